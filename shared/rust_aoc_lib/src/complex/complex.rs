@@ -1,7 +1,11 @@
+use crate::num::Num;
 use std::fmt::{Debug, Display};
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq, Hash, Default, Copy, Clone)]
+// Ordering is lexicographic: first by real part, then by imaginary part.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Copy, Clone)]
 pub struct Complex<T> {
     pub real: T,
     pub imag: T,
@@ -19,6 +23,90 @@ where
     }
 }
 
+impl<T> Complex<T>
+where
+    T: Num,
+{
+    pub const ZERO: Self = Self {
+        real: T::ZERO,
+        imag: T::ZERO,
+    };
+    pub const ONE: Self = Self {
+        real: T::ONE,
+        imag: T::ZERO,
+    };
+    pub const I: Self = Self {
+        real: T::ZERO,
+        imag: T::ONE,
+    };
+
+    pub fn new(real: T, imag: T) -> Self {
+        Self { real, imag }
+    }
+
+    // Squared absolute value, which stays exact for integers.
+    pub fn norm_sqr(&self) -> T {
+        self.real * self.real + self.imag * self.imag
+    }
+
+    // Sum of the absolute values of both parts, i.e. the Manhattan distance to 0.
+    pub fn manhattan(&self) -> T {
+        let abs = |x: T| if x < T::ZERO { T::ZERO - x } else { x };
+        abs(self.real) + abs(self.imag)
+    }
+}
+
+// Polar form and analytic functions, only for floats.
+macro_rules! impl_complex_float {
+    ($($t:ty),*) => {$(
+        impl Complex<$t> {
+            pub fn from_polar(r: $t, theta: $t) -> Self {
+                Self {
+                    real: r * theta.cos(),
+                    imag: r * theta.sin(),
+                }
+            }
+
+            // (abs, arg) such that self == from_polar(abs, arg).
+            pub fn to_polar(&self) -> ($t, $t) {
+                (self.abs(), self.arg())
+            }
+
+            pub fn abs(&self) -> $t {
+                self.real.hypot(self.imag)
+            }
+
+            // Angle to the positive real axis in (-pi, pi].
+            pub fn arg(&self) -> $t {
+                self.imag.atan2(self.real)
+            }
+
+            pub fn exp(&self) -> Self {
+                Self::from_polar(self.real.exp(), self.imag)
+            }
+
+            pub fn powi(&self, n: i32) -> Self {
+                let (r, theta) = self.to_polar();
+                Self::from_polar(r.powi(n), theta * n as $t)
+            }
+        }
+    )*};
+}
+
+impl_complex_float!(f32, f64);
+
+impl<T> From<T> for Complex<T>
+where
+    T: Num,
+{
+    fn from(real: T) -> Self {
+        Self {
+            real,
+            imag: T::ZERO,
+        }
+    }
+}
+
 impl<T> Display for Complex<T>
 where
     T: Display,
@@ -155,3 +243,246 @@ where
         }
     }
 }
+
+impl<T> AddAssign for Complex<T>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, other: Self) {
+        self.real += other.real;
+        self.imag += other.imag;
+    }
+}
+
+impl<T> AddAssign<T> for Complex<T>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, value: T) {
+        self.real += value;
+    }
+}
+
+impl<T> SubAssign for Complex<T>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, other: Self) {
+        self.real -= other.real;
+        self.imag -= other.imag;
+    }
+}
+
+impl<T> SubAssign<T> for Complex<T>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, value: T) {
+        self.real -= value;
+    }
+}
+
+impl<T> MulAssign for Complex<T>
+where
+    T: Mul<Output = T> + Add<Output = T> + Sub<Output = T> + Copy,
+{
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<T> MulAssign<T> for Complex<T>
+where
+    T: Mul<Output = T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> DivAssign for Complex<T>
+where
+    T: Mul<Output = T>
+        + Div<Output = T>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Neg<Output = T>
+        + Copy,
+{
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+impl<T> DivAssign<T> for Complex<T>
+where
+    T: Div<Output = T> + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T> Sum for Complex<T>
+where
+    T: Num,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, z| acc + z)
+    }
+}
+
+impl<'a, T> Sum<&'a Complex<T>> for Complex<T>
+where
+    T: Num,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T> Product for Complex<T>
+where
+    T: Num,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, z| acc * z)
+    }
+}
+
+impl<'a, T> Product<&'a Complex<T>> for Complex<T>
+where
+    T: Num,
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+// Parses the Display format "a + b * i" as well as forms like "3-4i", "-2i", "i" and "5".
+impl<T> FromStr for Complex<T>
+where
+    T: Num + FromStr,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let compact: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && c != &'*')
+            .collect();
+        let error = || format!("Invalid complex number {s:?}.");
+        let parse = |part: &str| part.parse::<T>().map_err(|_| error());
+        if !compact.is_ascii() {
+            return Err(error());
+        }
+
+        let Some(imag) = compact.strip_suffix('i') else {
+            return Ok(Self::from(parse(&compact)?));
+        };
+        // Split before the last sign that is neither leading, part of an exponent, nor a second sign.
+        let chars: Vec<char> = imag.chars().collect();
+        let split = (1..chars.len()).rev().find(|&k| {
+            matches!(chars[k], '+' | '-') && !matches!(chars[k - 1], 'e' | 'E' | '+' | '-')
+        });
+        let (real, imag) = match split {
+            Some(k) => (parse(&imag[..k])?, &imag[k..]),
+            None => (T::ZERO, imag),
+        };
+        let imag = imag.strip_prefix('+').unwrap_or(imag);
+        let imag = match imag {
+            "" => T::ONE,
+            // Through T's parser, which rejects it for unsigned types.
+            "-" => parse("-1")?,
+            _ => parse(imag)?,
+        };
+        Ok(Self { real, imag })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn assign_operations() {
+        let i = Complex::<i32>::I;
+        let mut pos = Complex::new(2, 3);
+        let mut dir = Complex::new(0, -1);
+
+        pos += dir;
+        dir *= i;
+        pos += dir * 2;
+        pos -= 1;
+        assert_eq!(pos, Complex::new(3, 2));
+        assert_eq!(dir, Complex::new(1, 0));
+
+        let mut z = Complex::new(6.0, 8.0);
+        z /= 2.0;
+        z -= Complex::new(1.0, 1.0);
+        z /= Complex::new(0.0, 1.0);
+        assert_eq!(z, Complex::new(3.0, -2.0));
+    }
+
+    #[test]
+    fn sums_and_products() {
+        let steps = [Complex::new(1, 0), Complex::new(0, 2), Complex::new(-3, 1)];
+        assert_eq!(steps.iter().sum::<Complex<i64>>(), Complex::new(-2, 3));
+        assert_eq!(steps.into_iter().sum::<Complex<i64>>(), Complex::new(-2, 3));
+
+        let i = Complex::<i64>::I;
+        assert_eq!([i; 4].iter().product::<Complex<i64>>(), Complex::ONE);
+        assert_eq!([i; 3].into_iter().product::<Complex<i64>>(), -i);
+    }
+
+    #[test]
+    fn ordering_and_norms() {
+        let mut points = vec![Complex::new(1, 5), Complex::new(0, 7), Complex::new(1, -2)];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![Complex::new(0, 7), Complex::new(1, -2), Complex::new(1, 5)]
+        );
+        assert_eq!(points.iter().max(), Some(&Complex::new(1, 5)));
+
+        let z = Complex::new(-3, 4);
+        assert_eq!(z.norm_sqr(), 25);
+        assert_eq!(z.manhattan(), 7);
+        assert_eq!(Complex::new(3_u8, 4).manhattan(), 7);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("3 + -4 * i".parse(), Ok(Complex::new(3, -4)));
+        assert_eq!("3-4i".parse(), Ok(Complex::new(3, -4)));
+        assert_eq!("-2i".parse(), Ok(Complex::new(0, -2)));
+        assert_eq!("-i".parse(), Ok(Complex::new(0, -1)));
+        assert!("-i".parse::<Complex<u8>>().is_err());
+        assert!("3-i".parse::<Complex<u8>>().is_err());
+        assert_eq!("i".parse(), Ok(Complex::new(0, 1)));
+        assert_eq!("17".parse(), Ok(Complex::new(17, 0)));
+        assert_eq!("1e-3+2.5e+1i".parse(), Ok(Complex::new(0.001, 25.0)));
+        assert!("3+x".parse::<Complex<i32>>().is_err());
+
+        let z = Complex::new(-1.5, 0.25);
+        assert_eq!(z.to_string().parse(), Ok(z));
+    }
+
+    #[test]
+    fn polar_form() {
+        let z = Complex::<f64>::from_polar(2.0, PI / 2.0);
+        assert!((z - Complex::new(0.0, 2.0)).abs() < 1e-12);
+        assert_eq!(Complex::new(3.0_f64, 4.0).abs(), 5.0);
+        assert_eq!(Complex::new(-1.0_f32, 0.0).arg(), std::f32::consts::PI);
+
+        // Euler's identity.
+        let e = Complex::new(0.0, PI).exp();
+        assert!((e + 1.0).abs() < 1e-12);
+
+        let w = Complex::new(1.0_f64, 1.0).powi(4);
+        assert!((w - Complex::new(-4.0, 0.0)).abs() < 1e-12);
+        let (r, theta) = Complex::new(0.0_f64, -2.0).to_polar();
+        assert_eq!((r, theta), (2.0, -PI / 2.0));
+    }
+}
@@ -0,0 +1,219 @@
+// Gaussian integers: complex numbers with integer parts, which share much of the
+// number theory of the integers. Division rounds the quotient to the nearest
+// lattice point, so remainders satisfy norm(r) <= norm(divisor) / 2.
+
+use super::Complex;
+use crate::num::Num;
+use std::ops::{Neg, Rem};
+
+impl<T> Complex<T>
+where
+    T: Num,
+{
+    // self^k by repeated squaring.
+    pub fn pow(self, mut k: u32) -> Self {
+        let (mut base, mut result) = (self, Self::ONE);
+        while k > 0 {
+            if k & 1 == 1 {
+                result *= base;
+            }
+            base = base * base;
+            k >>= 1;
+        }
+        return result;
+    }
+}
+
+impl<T> Complex<T>
+where
+    T: Num + Neg<Output = T>,
+{
+    // Multiply by I^quarter_turns. With y pointing down on a map, positive turns are clockwise.
+    pub fn rotate(self, quarter_turns: i64) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Self::new(-self.imag, self.real),
+            2 => -self,
+            _ => Self::new(self.imag, -self.real),
+        }
+    }
+}
+
+// Nearest integer to a / b for b > 0, rounding halves up.
+fn div_round(a: i128, b: i128) -> i128 {
+    let (q, r) = (a.div_euclid(b), a.rem_euclid(b));
+    if r >= b - r {
+        q + 1
+    } else {
+        q
+    }
+}
+
+impl Complex<i64> {
+    // Quotient and remainder such that self == q * other + r and norm(r) <= norm(other) / 2.
+    // Panics if other is zero.
+    pub fn div_rem(self, other: Self) -> (Self, Self) {
+        // All products in i128, as the norm alone may exceed i64.
+        let (a, b) = (self.real as i128, self.imag as i128);
+        let (c, d) = (other.real as i128, other.imag as i128);
+        let norm = c * c + d * d;
+        assert!(norm != 0, "Division by zero.");
+        // self / other = self * conj(other) / norm(other), rounded to the nearest lattice point.
+        let q = Self::new(
+            div_round(a * c + b * d, norm) as i64,
+            div_round(b * c - a * d, norm) as i64,
+        );
+        return (q, self - q * other);
+    }
+
+    // Whether self divides other, i.e. other is a multiple of self.
+    pub fn divides(self, other: Self) -> bool {
+        if self == Self::ZERO {
+            return other == Self::ZERO;
+        }
+        other.div_rem(self).1 == Self::ZERO
+    }
+
+    // The associate u * self, for a unit u in {1, I, -1, -I}, with real > 0 and imag >= 0.
+    pub fn canonical(self) -> Self {
+        (0..4)
+            .map(|k| self.rotate(k))
+            .find(|z| z.real > 0 && z.imag >= 0)
+            .unwrap_or(Self::ZERO)
+    }
+
+    pub fn is_unit(&self) -> bool {
+        self.norm_sqr() == 1
+    }
+
+    // Greatest common divisor in canonical form, by the Euclidean algorithm.
+    pub fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        while b != Self::ZERO {
+            (a, b) = (b, a.div_rem(b).1);
+        }
+        return a.canonical();
+    }
+
+    // self^k reduced modulo m after every multiplication.
+    pub fn pow_mod(self, mut k: u64, m: Self) -> Self {
+        let (mut base, mut result) = (self % m, Self::ONE % m);
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result * base % m;
+            }
+            base = base * base % m;
+            k >>= 1;
+        }
+        return result;
+    }
+
+    // Reduce both parts into 0..width and 0..height, i.e. walking on a torus.
+    pub fn wrap(self, width: i64, height: i64) -> Self {
+        Self::new(self.real.rem_euclid(width), self.imag.rem_euclid(height))
+    }
+}
+
+impl Rem for Complex<i64> {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        self.div_rem(other).1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn powers_of_i() {
+        let i = Complex::<i64>::I;
+        assert_eq!(i.pow(0), Complex::ONE);
+        assert_eq!(i.pow(2), Complex::new(-1, 0));
+        assert_eq!(i.pow(4001), i);
+        assert_eq!(Complex::new(1, 1).pow(8), Complex::new(16, 0));
+        assert_eq!(Complex::new(2, -1).pow(3), Complex::new(2, -11));
+
+        let z = Complex::new(3, -5);
+        for k in -5..5 {
+            assert_eq!(z.rotate(k), z * i.pow(k.rem_euclid(4) as u32));
+        }
+    }
+
+    #[test]
+    fn division() {
+        let a = Complex::new(27, -23);
+        let b = Complex::new(8, 1);
+        let (q, r) = a.div_rem(b);
+        assert_eq!(q * b + r, a);
+        assert!(2 * r.norm_sqr() <= b.norm_sqr());
+        assert_eq!(a % b, r);
+
+        // The norm of the divisor exceeds i64.
+        let (a, b) = (
+            Complex::new(10_000_000_000, 3),
+            Complex::new(5_000_000_000, 1),
+        );
+        assert_eq!(a.div_rem(b), (Complex::new(2, 0), Complex::new(0, 1)));
+
+        assert!(Complex::new(1, 1).divides(Complex::new(2, 0)));
+        assert!(!Complex::new(2, 0).divides(Complex::new(1, 1)));
+        assert!(!Complex::new(0, -3).is_unit() && Complex::new(0, -1).is_unit());
+    }
+
+    #[test]
+    fn gcd() {
+        // 5 = (2 + i)(2 - i) and 13 = (3 + 2i)(3 - 2i).
+        let a = Complex::new(2, 1) * Complex::new(3, 2) * Complex::new(1, 1);
+        let b = Complex::new(2, 1) * Complex::new(3, -2) * 7;
+        assert_eq!(a.gcd(b), Complex::new(2, 1));
+        assert_eq!(b.gcd(a), Complex::new(2, 1));
+        assert_eq!(Complex::new(0, -6).gcd(Complex::ZERO), Complex::new(6, 0));
+        assert_eq!(Complex::new(-1, 2).canonical(), Complex::new(2, 1));
+
+        let m = Complex::new(4, 1);
+        for k in 0..20 {
+            let z = Complex::new(2, 3);
+            assert_eq!(z.pow_mod(k, m), z.pow(k as u32) % m);
+        }
+    }
+
+    #[test]
+    fn lattice_walks() {
+        // Follow "move forward, then turn" instructions, with positive turns clockwise
+        // when y points down. Walking a square returns to the start.
+        let walk = |moves: &[(i64, i64)]| {
+            let (mut pos, mut dir) = (Complex::ZERO, Complex::new(1, 0));
+            for &(steps, turns) in moves {
+                pos += dir * steps;
+                dir = dir.rotate(turns);
+            }
+            (pos, dir)
+        };
+        assert_eq!(
+            walk(&[(3, 1), (3, 1), (3, 1), (3, 1)]),
+            (Complex::ZERO, Complex::new(1, 0))
+        );
+        assert_eq!(
+            walk(&[(2, 1), (5, -1), (1, 2)]),
+            (Complex::new(3, 5), Complex::new(-1, 0))
+        );
+
+        // A robot on a 11 x 7 torus, as in year 2024 day 14.
+        let (start, velocity) = (Complex::new(2, 4), Complex::new(2, -3));
+        let positions: Vec<Complex<i64>> = (1..=5)
+            .map(|t| (start + velocity * t).wrap(11, 7))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                Complex::new(4, 1),
+                Complex::new(6, 5),
+                Complex::new(8, 2),
+                Complex::new(10, 6),
+                Complex::new(1, 3)
+            ]
+        );
+    }
+}
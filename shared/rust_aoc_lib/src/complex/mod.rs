@@ -1,4 +1,5 @@
 mod complex;
+mod gaussian;
 mod scalar_operations;
 
-pub use complex::*;
\ No newline at end of file
+pub use complex::*;
@@ -1,52 +1,67 @@
 use super::Complex;
-use std::ops::{Add, Sub, Mul};
+use std::ops::{Add, Div, Mul, Sub};
 
-// These are unfortunately necessary for left addition and multiplication with a scalar
+// These are unfortunately necessary for left operations with a scalar, since
+// Rust cannot implement Add<Complex<T>> for a generic T.
 
-impl Add<Complex<f32>> for f32 {
-    type Output = Complex<f32>;
+macro_rules! impl_left_scalar_ops {
+    ($($t:ty),*) => {$(
+        impl Add<Complex<$t>> for $t {
+            type Output = Complex<$t>;
 
-    fn add(self, rhs: Complex<f32>) -> Self::Output {
-        rhs + self
-    }
-}
+            fn add(self, rhs: Complex<$t>) -> Self::Output {
+                rhs + self
+            }
+        }
 
-impl Add<Complex<i32>> for i32 {
-    type Output = Complex<i32>;
+        impl Sub<Complex<$t>> for $t {
+            type Output = Complex<$t>;
 
-    fn add(self, rhs: Complex<i32>) -> Self::Output {
-        rhs + self
-    }
-}
+            fn sub(self, rhs: Complex<$t>) -> Self::Output {
+                Complex::from(self) - rhs
+            }
+        }
 
-impl Sub<Complex<f32>> for f32 {
-    type Output = Complex<f32>;
+        impl Mul<Complex<$t>> for $t {
+            type Output = Complex<$t>;
 
-    fn sub(self, rhs: Complex<f32>) -> Self::Output {
-        -rhs + self
-    }
+            fn mul(self, rhs: Complex<$t>) -> Self::Output {
+                rhs * self
+            }
+        }
+    )*};
 }
 
-impl Sub<Complex<i32>> for i32 {
-    type Output = Complex<i32>;
+// Complex division needs negation, so only for signed types.
+macro_rules! impl_left_scalar_div {
+    ($($t:ty),*) => {$(
+        impl Div<Complex<$t>> for $t {
+            type Output = Complex<$t>;
 
-    fn sub(self, rhs: Complex<i32>) -> Self::Output {
-        -rhs + self
-    }
+            fn div(self, rhs: Complex<$t>) -> Self::Output {
+                Complex::from(self) / rhs
+            }
+        }
+    )*};
 }
 
-impl Mul<Complex<f32>> for f32 {
-    type Output = Complex<f32>;
+impl_left_scalar_ops!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+impl_left_scalar_div!(i8, i16, i32, i64, i128, isize, f32, f64);
 
-    fn mul(self, rhs: Complex<f32>) -> Self::Output {
-        rhs * self
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl Mul<Complex<i32>> for i32 {
-    type Output = Complex<i32>;
+    #[test]
+    fn left_scalars() {
+        let z = Complex::new(3_u8, 4);
+        assert_eq!(2 + z, Complex::new(5, 4));
+        assert_eq!(2 * z, Complex::new(6, 8));
 
-    fn mul(self, rhs: Complex<i32>) -> Self::Output {
-        rhs * self
+        let w = Complex::new(1_i64, -2);
+        assert_eq!(1 - w, Complex::new(0, 2));
+        assert_eq!(5 / w, Complex::new(1, 2));
+        assert_eq!(0.5 * Complex::new(4.0_f32, 2.0), Complex::new(2.0, 1.0));
+        assert_eq!(1.0 / Complex::new(0.0, 2.0), Complex::new(0.0, -0.5));
     }
 }
@@ -0,0 +1,54 @@
+use crate::num::Num;
+use std::ops::Add;
+
+// Path cost where infinity is distinct from every finite value, so that
+// unreachable nodes never need a sentinel like u64::MAX. Additions that
+// overflow saturate to Infinite. Variant order makes Finite < Infinite.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Cost<U> {
+    Finite(U),
+    Infinite,
+}
+
+impl<U: Num> Cost<U> {
+    pub const ZERO: Self = Cost::Finite(U::ZERO);
+
+    pub fn is_finite(&self) -> bool {
+        matches!(self, Cost::Finite(_))
+    }
+
+    pub fn finite(self) -> Option<U> {
+        match self {
+            Cost::Finite(value) => Some(value),
+            Cost::Infinite => None,
+        }
+    }
+}
+
+impl<U: Num> From<U> for Cost<U> {
+    fn from(value: U) -> Self {
+        Cost::Finite(value)
+    }
+}
+
+impl<U: Num> Add for Cost<U> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (Cost::Finite(a), Cost::Finite(b)) => match a.checked_add(b) {
+                Some(sum) => Cost::Finite(sum),
+                None => Cost::Infinite,
+            },
+            _ => Cost::Infinite,
+        }
+    }
+}
+
+impl<U: Num> Add<U> for Cost<U> {
+    type Output = Self;
+
+    fn add(self, other: U) -> Self {
+        self + Cost::Finite(other)
+    }
+}
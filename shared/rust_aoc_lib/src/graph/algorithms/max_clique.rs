@@ -1,10 +1,10 @@
-use super::Graph;
+use super::{EdgeType, Graph};
 use std::collections::HashSet;
 use std::hash::Hash;
 
-pub fn max_cliques<T: Hash + Eq + Copy + Clone>(
+pub fn max_cliques<T: Hash + Eq + Copy + Clone, D: EdgeType>(
     nodes: HashSet<T>,
-    graph: &Graph<T, ()>,
+    graph: &Graph<T, (), D>,
 ) -> Vec<HashSet<T>> {
     // Bron-Kerbosch Algorithm for Maximal Cliques Detection
     // Computes the maximal clique for all nodes in `nodes`. If all nodes of `graph` are 
@@ -13,11 +13,11 @@ pub fn max_cliques<T: Hash + Eq + Copy + Clone>(
     bron_kerbosch(HashSet::new(), nodes, HashSet::new(), graph)
 }
 
-fn bron_kerbosch<T: Hash + Eq + Copy + Clone>(
+fn bron_kerbosch<T: Hash + Eq + Copy + Clone, D: EdgeType>(
     clique: HashSet<T>,
     mut unchecked: HashSet<T>,
     mut checked: HashSet<T>,
-    graph: &Graph<T, ()>,
+    graph: &Graph<T, (), D>,
 ) -> Vec<HashSet<T>> {
     if unchecked.is_empty() && checked.is_empty() {
         return vec![clique];
@@ -1,12 +1,19 @@
-use super::{Graph, Num};
+use super::{Cost, EdgeType, Graph, Num};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
-use std::ops::{Add, Sub};
 
-pub fn max_flow<T, U>(graph: &Graph<T, U>, source: &T, sink: &T) -> Option<(U, Graph<T, U>)>
+// Partition of the nodes of a graph into two disjoint sets.
+type Partition<T> = (HashSet<T>, HashSet<T>);
+
+pub fn max_flow<T, U, D>(
+    graph: &Graph<T, U, D>,
+    source: &T,
+    sink: &T,
+) -> Option<(U, Graph<T, U>)>
 where
     T: Eq + Hash + Clone,
-    U: Num<U> + Copy + Clone + PartialOrd + Add<Output = U> + Sub<Output = U>,
+    U: Num,
+    D: EdgeType,
 {
     // Computes the maximal flow and flow function as a graph using
     // Edmond Karp: https://en.wikipedia.org/wiki/Ford%E2%80%93Fulkerson_algorithm.
@@ -15,12 +22,13 @@ where
     let mut flow_amount = U::ZERO;
     let mut flow = Graph::new();
 
-    // Built flow graph.
+    // Built flow graph, with arcs both ways for the residual flow.
     flow.add_node(source.clone());
     flow.add_node(sink.clone());
     for node in graph.iter() {
-        for neighbour in graph.neighbours::<Vec<_>>(&node) {
-            flow.add_edge(node.clone(), neighbour, U::ZERO);
+        for neighbour in graph.neighbours::<Vec<_>>(node) {
+            flow.add_directed_edge(node.clone(), neighbour.clone(), U::ZERO);
+            flow.add_directed_edge(neighbour, node.clone(), U::ZERO);
         }
     }
 
@@ -52,32 +60,26 @@ where
             return Some((flow_amount, flow));
         } else {
             // Have found a path with capacity
-            let mut path_capacity = U::INF;
+            let mut path_capacity = Cost::Infinite;
             let mut vertex = sink;
             while let Some((predecessor, capacity)) = predecessor.get(vertex) {
-                path_capacity = if path_capacity < *capacity {
-                    path_capacity
-                } else {
-                    *capacity
-                };
+                if Cost::Finite(*capacity) < path_capacity {
+                    path_capacity = Cost::Finite(*capacity);
+                }
                 vertex = predecessor;
             }
+            // The path has at least one edge, so its capacity is finite.
+            let path_capacity = path_capacity.finite().unwrap();
             // Add this path to flow
             flow_amount = flow_amount + path_capacity;
             vertex = sink;
-            while let Some((predecessor, _)) = predecessor.get(&vertex) {
+            while let Some((predecessor, _)) = predecessor.get(vertex) {
                 // Add flow to predecessor -> vertex
-                flow.data.entry(predecessor.clone()).and_modify(|map| {
-                    map.entry(vertex.clone()).and_modify(|val| {
-                        *val = *val + path_capacity;
-                    });
-                });
+                let forward = flow.weight(predecessor, vertex) + path_capacity;
+                flow.add_directed_edge(predecessor.clone(), vertex.clone(), forward);
                 // Remove flow from vertex -> predecessor
-                flow.data.entry(vertex.clone()).and_modify(|map| {
-                    map.entry(predecessor.clone()).and_modify(|val| {
-                        *val = *val - path_capacity;
-                    });
-                });
+                let backward = flow.weight(vertex, predecessor) - path_capacity;
+                flow.add_directed_edge(vertex.clone(), predecessor.clone(), backward);
 
                 vertex = predecessor;
             }
@@ -85,10 +87,15 @@ where
     }
 }
 
-pub fn min_cut<T, U>(graph: &Graph<T, U>, a: &T, b: &T) -> Option<(U, (HashSet<T>, HashSet<T>))>
+pub fn min_cut<T, U, D>(
+    graph: &Graph<T, U, D>,
+    a: &T,
+    b: &T,
+) -> Option<(U, Partition<T>)>
 where
     T: Eq + Hash + Clone,
-    U: Num<U> + Copy + Clone + PartialOrd + Add<Output = U> + Sub<Output = U>,
+    U: Num,
+    D: EdgeType,
 {
     // Computes the capacity of the min-cut and a partition induced by the cut.
     // The partition is in general non-unique and will only include nodes connected to a and b.
@@ -106,8 +113,8 @@ where
     while let Some(vertex) = queue.pop_front() {
         let flows = &flow[vertex];
         for (neighbour, capacity) in &graph[vertex] {
-            if !g1.contains(&neighbour) && capacity > &flows[&neighbour] {
-                queue.push_back(&neighbour);
+            if !g1.contains(neighbour) && capacity > &flows[neighbour] {
+                queue.push_back(neighbour);
                 g1.insert(neighbour.clone());
             }
         }
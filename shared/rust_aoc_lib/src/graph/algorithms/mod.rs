@@ -1,33 +1,15 @@
+mod cost;
+mod max_clique;
 mod max_flow_min_cut;
 mod pathfinding;
-mod max_clique;
 
+pub use cost::*;
+pub use max_clique::*;
 pub use max_flow_min_cut::*;
 pub use pathfinding::*;
-pub use max_clique::*;
 
-use super::Graph;
-
-// Internal trait to define the default values of numbers
-pub trait Num<T> {
-    const ZERO: T;
-    const INF: T;
-}
-
-impl Num<f64> for f64 {
-    const ZERO: f64 = 0.0;
-    const INF: f64 = f64::INFINITY;
-}
-
-impl Num<i64> for i64 {
-    const ZERO: i64 = 0;
-    const INF: i64 = i64::MAX;
-}
-
-impl Num<u64> for u64 {
-    const ZERO: u64 = 0;
-    const INF: u64 = u64::MAX;
-}
+use super::{EdgeType, Graph};
+use crate::num::Num;
 
 #[cfg(test)]
 mod tests {
@@ -117,6 +99,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn a_star_small_weights() {
+        let mut graph: Graph<u8, u32> = Graph::new();
+        graph.add_directed_edge(0, 1, u32::MAX - 1);
+        graph.add_directed_edge(1, 2, 5);
+        graph.add_directed_edge(0, 2, 7);
+
+        let end_condition = |node: &u8| node == &2;
+        let res = a_star(&graph, &0, &end_condition, None);
+        assert_eq!(res, Some((7, vec![0, 2])));
+    }
+
+    #[test]
+    fn costs() {
+        let max = Cost::Finite(u64::MAX);
+        assert!(Cost::Finite(0) < max && max < Cost::Infinite);
+        assert_eq!(max + 1, Cost::Infinite);
+        assert_eq!(Cost::Finite(2_u8) + Cost::Finite(3), Cost::Finite(5));
+        assert_eq!(Cost::Infinite + Cost::Finite(0_i32), Cost::Infinite);
+        assert_eq!(Cost::Finite(-1.5) + 0.5, Cost::Finite(-1.0));
+    }
+
+    #[test]
+    fn a_star_large_weights() {
+        let big = u64::MAX / 2;
+        let mut graph: Graph<char, u64> = Graph::new();
+        graph.add_directed_edge('A', 'B', big);
+        graph.add_directed_edge('B', 'C', big);
+        graph.add_directed_edge('A', 'D', 1);
+        graph.add_directed_edge('D', 'C', big + 1);
+        graph.add_directed_edge('C', 'E', big);
+
+        // An admissible heuristic, the cheapest remaining edge, with the score near u64::MAX.
+        let heuristic = |node: &char| if node == &'C' { 0 } else { big };
+        let end_condition = |node: &char| node == &'C';
+        let res = a_star(&graph, &'A', &end_condition, Some(&heuristic));
+        assert_eq!(res, Some((big + 2, vec!['A', 'D', 'C'])));
+        assert_eq!(a_star(&graph, &'A', &end_condition, None), res);
+
+        // Scores past u64::MAX saturate rather than panic, even for a heuristic that
+        // overestimates.
+        let heuristic = |node: &char| if node == &'C' { 0 } else { u64::MAX };
+        let res = a_star(&graph, &'A', &end_condition, Some(&heuristic));
+        assert!(matches!(res, Some((_, path)) if path.last() == Some(&'C')));
+
+        // Paths longer than u64::MAX are unreachable.
+        let end_condition = |node: &char| node == &'E';
+        assert_eq!(a_star(&graph, &'A', &end_condition, None), None);
+    }
+
     #[test]
     fn min_cut_floats() {
         // Example taken from https://en.wikipedia.org/wiki/Edmonds%E2%80%93Karp_algorithm
@@ -133,4 +165,19 @@ mod tests {
         let res = a_star(&graph, &'A', &end_condition, None);
         assert_eq!(res, None);
     }
+
+    #[test]
+    fn multi_source_distances() {
+        let mut graph: Graph<char, u32> = Graph::new();
+        graph.add_directed_edge('A', 'B', 4);
+        graph.add_directed_edge('B', 'C', 1);
+        graph.add_directed_edge('D', 'B', 1);
+        graph.add_directed_edge('E', 'A', 1);
+
+        let dist = distances(&graph, &['A', 'D']);
+        assert_eq!(
+            dist,
+            HashMap::from([('A', 0), ('B', 1), ('C', 2), ('D', 0)])
+        );
+    }
 }
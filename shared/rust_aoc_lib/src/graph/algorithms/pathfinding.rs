@@ -1,4 +1,4 @@
-use super::{Num, Graph};
+use super::{Cost, EdgeType, Graph, Num};
 use std::collections::{BinaryHeap, HashMap};
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -9,10 +9,11 @@ pub trait Traversible<T, U> {
     fn connections(&self, node: &T) -> HashMap<T, U>;
 }
 
-impl<T, U> Traversible<T, U> for Graph<T, U>
+impl<T, U, D> Traversible<T, U> for Graph<T, U, D>
 where
     T: Clone + std::hash::Hash + Eq,
     U: Default + Clone,
+    D: EdgeType,
 {
     fn connections(&self, node: &T) -> HashMap<T, U> {
         self[node].clone()
@@ -73,23 +74,24 @@ pub fn a_star<T, U>(
 ) -> Option<(U, Vec<T>)>
 where
     T: Eq + Hash + Clone,
-    U: Num<U> + Copy + Clone + std::ops::Add<Output = U> + PartialOrd,
+    U: Num,
 {
     // Without a heuristic this simply becomes Dijkstra's.
     // Heuristic should be admissible => underestimates the actual cost.
     let heuristic = heuristic.unwrap_or(&|_| U::ZERO);
 
     // data holds tuples (shortest_distance, Some(predecessor)).
-    let mut data = HashMap::<T, (U, Option<T>)>::from([(start.clone(), (U::ZERO, None))]);
+    // Nodes without an entry are at infinite distance.
+    let mut data = HashMap::<T, (Cost<U>, Option<T>)>::from([(start.clone(), (Cost::ZERO, None))]);
     let mut queue = BinaryHeap::from([State {
         node: start.clone(),
-        score: U::ZERO,
+        score: Cost::ZERO,
     }]);
     while let Some(state) = queue.pop() {
         let current = state.node;
 
         if end_condition(&current) {
-            let end_distance = data[&current].0;
+            let end_distance = data[&current].0.finite()?;
             let end_path: Vec<T> = reconstruct_path(&data, current.clone());
             return Some((end_distance, end_path));
         }
@@ -97,13 +99,17 @@ where
         for (next, weight) in graph.connections(&current) {
             // Distance to next through current.
             let d = data[&current].0 + weight;
-            // If entry does not exist, fill with default value.
-            let entry = data.entry(next.clone()).or_insert((U::INF, None));
+            if !d.is_finite() {
+                // Longer than any representable distance.
+                continue;
+            }
+            let entry = data.entry(next.clone()).or_insert((Cost::Infinite, None));
 
             if d < entry.0 {
                 // This path is shorter than previous best.
                 entry.0 = d;
                 entry.1 = Some(current.clone());
+                // The score may still saturate to infinity through the heuristic.
                 queue.push(State {
                     node: next.clone(),
                     score: d + heuristic(&next),
@@ -113,3 +119,41 @@ where
     }
     return None;
 }
+
+// Dijkstra's from every node in starts at once. Returns the shortest distance
+// from the nearest start to every reachable node.
+pub fn distances<T, U>(graph: &dyn Traversible<T, U>, starts: &[T]) -> HashMap<T, U>
+where
+    T: Eq + Hash + Clone,
+    U: Num,
+{
+    let mut dist = HashMap::<T, Cost<U>>::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        dist.insert(start.clone(), Cost::ZERO);
+        queue.push(State {
+            node: start.clone(),
+            score: Cost::ZERO,
+        });
+    }
+    while let Some(State { node, score }) = queue.pop() {
+        if score > dist[&node] {
+            // Stale queue entry.
+            continue;
+        }
+        for (next, weight) in graph.connections(&node) {
+            let d = score + weight;
+            if d.is_finite() && d < *dist.get(&next).unwrap_or(&Cost::Infinite) {
+                dist.insert(next.clone(), d);
+                queue.push(State {
+                    node: next,
+                    score: d,
+                });
+            }
+        }
+    }
+    return dist
+        .into_iter()
+        .filter_map(|(node, d)| Some((node, d.finite()?)))
+        .collect();
+}
@@ -1,32 +1,66 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
+
+// Type-level marker for whether a graph is directed or undirected.
+pub trait EdgeType: Debug + Clone + Copy {
+    const DIRECTED: bool;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Directed {}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Undirected {}
+
+impl EdgeType for Directed {
+    const DIRECTED: bool = true;
+}
+
+impl EdgeType for Undirected {
+    const DIRECTED: bool = false;
+}
 
 #[derive(Debug, Clone)]
-pub struct Graph<T, U>
+pub struct Graph<T, U, D = Directed>
 where
     T: Clone + Hash + Eq,
     U: Clone,
+    D: EdgeType,
 {
     // A weighted graph over nodes: T with edge weights: U.
-    // For an undirected graph, simply let weights be unit: ().
+    // For an unweighted graph, simply let weights be unit: ().
     //     This is equivalent to using HashSet.
-    pub data: HashMap<T, HashMap<T, U>>,
+    // Edges are stored as arcs from -> to; an undirected edge is a pair of arcs.
+    data: HashMap<T, HashMap<T, U>>,
+    // Incoming arcs, such that removing a node never leaves dangling arcs.
+    pred: HashMap<T, HashSet<T>>,
+    kind: PhantomData<D>,
 }
 
+pub type DiGraph<T, U> = Graph<T, U, Directed>;
+pub type UnGraph<T, U> = Graph<T, U, Undirected>;
+
 // Graph manipulations
-impl<T, U> Graph<T, U>
+impl<T, U, D> Graph<T, U, D>
 where
     T: Clone + Hash + Eq,
     U: Clone,
+    D: EdgeType,
 {
-    // Create new empty graph.
-    pub fn new() -> Self {
+    fn empty() -> Self {
         Self {
             data: HashMap::new(),
+            pred: HashMap::new(),
+            kind: PhantomData,
         }
     }
 
+    pub fn is_directed(&self) -> bool {
+        D::DIRECTED
+    }
+
     // Iterate through nodes in random order.
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.data.keys()
@@ -37,6 +71,21 @@ where
         self.data.keys().cloned().collect()
     }
 
+    pub fn node_count(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn contains_node(&self, node: &T) -> bool {
+        self.data.contains_key(node)
+    }
+
+    pub fn contains_edge(&self, from: &T, to: &T) -> bool {
+        self.data
+            .get(from)
+            .is_some_and(|neighbours| neighbours.contains_key(to))
+    }
+
+    // For a directed graph these are the successors of node.
     pub fn neighbours<A: FromIterator<T>>(&self, node: &T) -> A {
         self.data
             .get(node)
@@ -54,57 +103,148 @@ where
     pub fn add_node(&mut self, node: T) -> bool {
         if self.data.contains_key(&node) {
             return false;
-        } else {
-            self.data.insert(node, HashMap::new());
-            return true;
         }
+        self.pred.insert(node.clone(), HashSet::new());
+        self.data.insert(node, HashMap::new());
+        return true;
     }
 
-    // Remove node and all its edges.
+    // Remove node and all its edges, both outgoing and incoming.
+    // Returns the outgoing edges of the removed node.
     pub fn remove_node(&mut self, node: &T) -> Option<HashMap<T, U>> {
-        match self.data.remove(node) {
-            None => return None,
-            Some(neighbours) => {
-                for (neighbour, _) in &neighbours {
-                    self.remove_directed_edge(neighbour, node);
-                }
-                return Some(neighbours);
+        let neighbours = self.data.remove(node)?;
+        for neighbour in neighbours.keys() {
+            if let Some(pred) = self.pred.get_mut(neighbour) {
+                pred.remove(node);
             }
         }
+        for predecessor in self.pred.remove(node).unwrap_or_default() {
+            if let Some(map) = self.data.get_mut(&predecessor) {
+                map.remove(node);
+            }
+        }
+        return Some(neighbours);
+    }
+
+    // Add arc from -> to. Create nodes if not present.
+    pub(super) fn insert_arc(&mut self, from: T, to: T, weight: U) {
+        self.add_node(from.clone());
+        self.add_node(to.clone());
+        self.pred.get_mut(&to).unwrap().insert(from.clone());
+        self.data.get_mut(&from).unwrap().insert(to, weight);
+    }
+
+    pub(super) fn delete_arc(&mut self, from: &T, to: &T) -> Option<U> {
+        let weight = self.data.get_mut(from)?.remove(to)?;
+        self.pred.get_mut(to).unwrap().remove(from);
+        return Some(weight);
+    }
+}
+
+// Directed-only operations
+impl<T, U> Graph<T, U, Directed>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+{
+    // Create new empty directed graph.
+    pub fn new() -> Self {
+        Self::empty()
     }
 
     // Add edge from -> to. Create nodes if not present.
     pub fn add_directed_edge(&mut self, from: T, to: T, weight: U) {
-        self.add_node(to.clone());
-        self.data
-            .entry(from)
-            .or_insert(HashMap::new())
-            .insert(to, weight);
+        self.insert_arc(from, to, weight);
     }
     pub fn remove_directed_edge(&mut self, from: &T, to: &T) -> Option<U> {
-        match self.data.get_mut(from) {
-            Some(neighbours) => neighbours.remove(to),
-            None => None,
+        self.delete_arc(from, to)
+    }
+
+    pub fn successors<A: FromIterator<T>>(&self, node: &T) -> A {
+        self.neighbours(node)
+    }
+
+    pub fn predecessors<A: FromIterator<T>>(&self, node: &T) -> A {
+        self.pred
+            .get(node)
+            .unwrap_or(&HashSet::new())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn out_degree(&self, node: &T) -> usize {
+        self.data.get(node).map_or(0, |map| map.len())
+    }
+
+    pub fn in_degree(&self, node: &T) -> usize {
+        self.pred.get(node).map_or(0, |set| set.len())
+    }
+
+    // New graph with the direction of every edge reversed.
+    pub fn reverse(&self) -> Self {
+        let mut graph = Self::new();
+        for (from, neighbours) in &self.data {
+            graph.add_node(from.clone());
+            for (to, weight) in neighbours {
+                graph.insert_arc(to.clone(), from.clone(), weight.clone());
+            }
         }
+        return graph;
+    }
+}
+
+// Undirected-only operations
+impl<T, U> Graph<T, U, Undirected>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+{
+    // Create new empty undirected graph.
+    pub fn new_undirected() -> Self {
+        Self::empty()
     }
 
-    // Add edge u -> v and v -> u. Create nodes if not present.
+    // Add edge between u and v. Create nodes if not present.
     pub fn add_edge(&mut self, u: T, v: T, weight: U) {
-        self.add_directed_edge(u.clone(), v.clone(), weight.clone());
-        self.add_directed_edge(v, u, weight);
+        self.insert_arc(u.clone(), v.clone(), weight.clone());
+        self.insert_arc(v, u, weight);
+    }
+    pub fn remove_edge(&mut self, u: &T, v: &T) -> Option<U> {
+        self.delete_arc(v, u);
+        self.delete_arc(u, v)
     }
-    pub fn remove_edge(&mut self, u: &T, v: &T) -> (Option<U>, Option<U>) {
-        (
-            self.remove_directed_edge(u, v),
-            self.remove_directed_edge(v, u),
-        )
+
+    pub fn degree(&self, node: &T) -> usize {
+        self.data.get(node).map_or(0, |map| map.len())
+    }
+
+    // Directed graph with an arc each way for every edge.
+    pub fn to_directed(&self) -> Graph<T, U, Directed> {
+        Graph {
+            data: self.data.clone(),
+            pred: self.pred.clone(),
+            kind: PhantomData,
+        }
     }
 }
 
-impl<T, U> std::ops::Index<&T> for Graph<T, U>
+impl<T, U, D> Default for Graph<T, U, D>
 where
     T: Clone + Hash + Eq,
     U: Clone,
+    D: EdgeType,
+{
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T, U, D> std::ops::Index<&T> for Graph<T, U, D>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+    D: EdgeType,
 {
     type Output = HashMap<T, U>;
 
@@ -114,21 +254,6 @@ where
     }
 }
 
-pub fn grid_graph_2d(n: usize, m: usize) -> Graph<(usize, usize), u64> {
-    let mut graph = Graph::new();
-    for i in 0..n - 1 {
-        for j in 0..m {
-            graph.add_edge((i, j), (i + 1, j), 1);
-        }
-    }
-    for j in 0..m - 1 {
-        for i in 0..n {
-            graph.add_edge((i, j), (i, j + 1), 1);
-        }
-    }
-    return graph;
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,13 +261,13 @@ mod tests {
 
     #[test]
     fn graph_manipulations() {
-        let mut graph: Graph<u8, ()> = Graph::new();
+        let mut graph: UnGraph<u8, ()> = UnGraph::new_undirected();
         graph.add_edge(0, 1, ());
         graph.add_edge(1, 2, ());
-        graph.remove_node(1);
+        graph.remove_node(&1);
 
         // Removing edges should not create nodes.
-        graph.remove_edge(0, 10);
+        graph.remove_edge(&0, &10);
 
         assert_eq!(graph.nodes::<HashSet<u8>>(), HashSet::from([0, 2]));
         assert_eq!(graph.neighbours::<Vec<u8>>(&0).len(), 0);
@@ -150,27 +275,52 @@ mod tests {
     }
 
     #[test]
-    fn grid_graph() {
-        let graph = grid_graph_2d(2, 3);
+    fn directed_graph() {
+        let mut graph: DiGraph<char, i32> = DiGraph::new();
+        graph.add_directed_edge('a', 'b', 1);
+        graph.add_directed_edge('c', 'b', 2);
+        graph.add_directed_edge('b', 'd', 3);
 
-        // Check nodes
+        assert!(graph.is_directed());
+        assert_eq!(graph.in_degree(&'b'), 2);
+        assert_eq!(graph.out_degree(&'b'), 1);
         assert_eq!(
-            graph.nodes::<HashSet<_>>(),
-            HashSet::from([(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)])
+            graph.predecessors::<HashSet<_>>(&'b'),
+            HashSet::from(['a', 'c'])
         );
 
-        // Check connections
-        assert_eq!(
-            graph.neighbours::<HashSet<_>>(&(0, 0)),
-            HashSet::from([(0, 1), (1, 0)])
-        );
+        let reversed = graph.reverse();
         assert_eq!(
-            graph.neighbours::<HashSet<_>>(&(1, 2)),
-            HashSet::from([(0, 2), (1, 1)])
-        );
-        assert_eq!(
-            graph.neighbours::<HashSet<_>>(&(1, 1)),
-            HashSet::from([(0, 1), (1, 0), (1, 2)])
+            reversed.successors::<HashSet<_>>(&'b'),
+            HashSet::from(['a', 'c'])
         );
+        assert_eq!(reversed.weight(&'d', &'b'), 3);
+
+        // Incoming arcs must not be left dangling.
+        graph.remove_node(&'b');
+        assert!(!graph.contains_edge(&'a', &'b'));
+        assert!(!graph.contains_edge(&'c', &'b'));
+        assert_eq!(graph.in_degree(&'d'), 0);
+    }
+
+    #[test]
+    fn undirected_graph() {
+        let mut graph: UnGraph<u8, ()> = UnGraph::new_undirected();
+        graph.add_edge(0, 1, ());
+        graph.add_edge(0, 2, ());
+
+        assert!(!graph.is_directed());
+        assert_eq!(graph.degree(&0), 2);
+        assert_eq!(graph.degree(&1), 1);
+
+        assert_eq!(graph.remove_edge(&1, &0), Some(()));
+        assert_eq!(graph.degree(&0), 1);
+        assert!(!graph.contains_edge(&0, &1));
+
+        // Each remaining edge becomes a pair of arcs.
+        let directed = graph.to_directed();
+        assert!(directed.is_directed());
+        assert_eq!(directed.in_degree(&0), 1);
+        assert_eq!(directed.predecessors::<Vec<_>>(&2), vec![0]);
     }
 }
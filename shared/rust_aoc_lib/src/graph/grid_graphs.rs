@@ -0,0 +1,272 @@
+use super::Graph;
+
+// Which cells count as neighbours: Orthogonal gives 4 neighbours in 2D and 6 in 3D,
+// Diagonal additionally includes diagonals for 8 neighbours in 2D and 26 in 3D.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Connectivity {
+    Orthogonal,
+    Diagonal,
+}
+
+// Unit-weight arcs both ways between orthogonal neighbours, such that single
+// directions can be removed afterwards, e.g. for one-way slopes.
+pub fn grid_graph_2d(n: usize, m: usize) -> Graph<(usize, usize), u64> {
+    let mut graph = Graph::new();
+    for i in 0..n {
+        for j in 0..m {
+            graph.add_node((i, j));
+        }
+    }
+    for i in 0..n.saturating_sub(1) {
+        for j in 0..m {
+            graph.add_directed_edge((i, j), (i + 1, j), 1);
+            graph.add_directed_edge((i + 1, j), (i, j), 1);
+        }
+    }
+    for j in 0..m.saturating_sub(1) {
+        for i in 0..n {
+            graph.add_directed_edge((i, j), (i, j + 1), 1);
+            graph.add_directed_edge((i, j + 1), (i, j), 1);
+        }
+    }
+    return graph;
+}
+
+pub fn grid_graph_3d(n: usize, m: usize, l: usize) -> Graph<(usize, usize, usize), u64> {
+    let grid = vec![vec![vec![(); l]; m]; n];
+    weighted_grid_graph_3d(&grid, &|_| Some(1), Connectivity::Orthogonal, false)
+}
+
+// Unit-weight graph over the cells of a character map that are passable.
+pub fn grid_graph_from_chars(
+    grid: &[Vec<char>],
+    passable: &dyn Fn(char) -> bool,
+    connectivity: Connectivity,
+    wrap: bool,
+) -> Graph<(usize, usize), u64> {
+    weighted_grid_graph(grid, &|&c| passable(c).then_some(1), connectivity, wrap)
+}
+
+// Graph over a rectangular grid where the edge a -> b costs the weight of entering b.
+// Cells with weight None are impassable. With wrap the grid is a torus.
+// Panics if the rows are not all of equal length.
+pub fn weighted_grid_graph<V, U: Clone>(
+    grid: &[Vec<V>],
+    weight: &dyn Fn(&V) -> Option<U>,
+    connectivity: Connectivity,
+    wrap: bool,
+) -> Graph<(usize, usize), U> {
+    let m = grid.first().map_or(0, |row| row.len());
+    assert!(
+        grid.iter().all(|row| row.len() == m),
+        "Grid rows must have equal length."
+    );
+    let cell = |pos: &[usize]| weight(&grid[pos[0]][pos[1]]);
+    let key = |pos: &[usize]| (pos[0], pos[1]);
+    return build_grid_graph(&[grid.len(), m], &cell, &key, connectivity, wrap);
+}
+
+// Panics if the planes or rows are not all of equal size.
+pub fn weighted_grid_graph_3d<V, U: Clone>(
+    grid: &[Vec<Vec<V>>],
+    weight: &dyn Fn(&V) -> Option<U>,
+    connectivity: Connectivity,
+    wrap: bool,
+) -> Graph<(usize, usize, usize), U> {
+    let m = grid.first().map_or(0, |plane| plane.len());
+    let l = grid
+        .first()
+        .and_then(|plane| plane.first())
+        .map_or(0, |row| row.len());
+    assert!(
+        grid.iter()
+            .all(|plane| plane.len() == m && plane.iter().all(|row| row.len() == l)),
+        "Grid planes and rows must have equal length."
+    );
+    let cell = |pos: &[usize]| weight(&grid[pos[0]][pos[1]][pos[2]]);
+    let key = |pos: &[usize]| (pos[0], pos[1], pos[2]);
+    return build_grid_graph(&[grid.len(), m, l], &cell, &key, connectivity, wrap);
+}
+
+// Nodes are the keys of the passable positions, one per cell of shape.
+fn build_grid_graph<K, U>(
+    shape: &[usize],
+    weight: &dyn Fn(&[usize]) -> Option<U>,
+    key: &dyn Fn(&[usize]) -> K,
+    connectivity: Connectivity,
+    wrap: bool,
+) -> Graph<K, U>
+where
+    K: Clone + std::hash::Hash + Eq,
+    U: Clone,
+{
+    let offsets = neighbour_offsets(shape.len(), connectivity);
+
+    let mut graph = Graph::new();
+    for pos in all_positions(shape) {
+        if weight(&pos).is_none() {
+            continue;
+        }
+        graph.add_node(key(&pos));
+        for offset in &offsets {
+            let Some(next) = step(&pos, offset, shape, wrap) else {
+                continue;
+            };
+            if next == pos {
+                // Wrapping around a dimension of size one.
+                continue;
+            }
+            if let Some(w) = weight(&next) {
+                graph.add_directed_edge(key(&pos), key(&next), w);
+            }
+        }
+    }
+    return graph;
+}
+
+fn neighbour_offsets(dims: usize, connectivity: Connectivity) -> Vec<Vec<isize>> {
+    let mut offsets = vec![vec![]];
+    for _ in 0..dims {
+        offsets = offsets
+            .into_iter()
+            .flat_map(|offset: Vec<isize>| [-1, 0, 1].map(|d| [offset.clone(), vec![d]].concat()))
+            .collect();
+    }
+    let max_nonzero = match connectivity {
+        Connectivity::Orthogonal => 1,
+        Connectivity::Diagonal => dims,
+    };
+    offsets.retain(|offset| {
+        let nonzero = offset.iter().filter(|&&d| d != 0).count();
+        nonzero > 0 && nonzero <= max_nonzero
+    });
+    return offsets;
+}
+
+fn all_positions(shape: &[usize]) -> Vec<Vec<usize>> {
+    let mut positions = vec![vec![]];
+    for &size in shape {
+        positions = positions
+            .into_iter()
+            .flat_map(|pos: Vec<usize>| (0..size).map(move |i| [pos.clone(), vec![i]].concat()))
+            .collect();
+    }
+    return positions;
+}
+
+fn step(pos: &[usize], offset: &[isize], shape: &[usize], wrap: bool) -> Option<Vec<usize>> {
+    let mut next = Vec::with_capacity(pos.len());
+    for ((&x, &d), &size) in pos.iter().zip(offset).zip(shape) {
+        let x = x as isize + d;
+        if wrap {
+            next.push(x.rem_euclid(size as isize) as usize);
+        } else if 0 <= x && x < size as isize {
+            next.push(x as usize);
+        } else {
+            return None;
+        }
+    }
+    return Some(next);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn grid_graph() {
+        let graph = grid_graph_2d(2, 3);
+
+        // Check nodes
+        assert_eq!(
+            graph.nodes::<HashSet<_>>(),
+            HashSet::from([(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)])
+        );
+
+        // Check connections
+        assert_eq!(
+            graph.neighbours::<HashSet<_>>(&(0, 0)),
+            HashSet::from([(0, 1), (1, 0)])
+        );
+        assert_eq!(
+            graph.neighbours::<HashSet<_>>(&(1, 2)),
+            HashSet::from([(0, 2), (1, 1)])
+        );
+        assert_eq!(
+            graph.neighbours::<HashSet<_>>(&(1, 1)),
+            HashSet::from([(0, 1), (1, 0), (1, 2)])
+        );
+    }
+
+    #[test]
+    fn char_grid_graph() {
+        let grid: Vec<Vec<char>> = ["..#", ".#.", "..."]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        let passable = |c| c != '#';
+
+        let graph = grid_graph_from_chars(&grid, &passable, Connectivity::Orthogonal, false);
+        assert_eq!(graph.node_count(), 7);
+        assert_eq!(
+            graph.neighbours::<HashSet<_>>(&(1, 2)),
+            HashSet::from([(2, 2)])
+        );
+
+        let graph = grid_graph_from_chars(&grid, &passable, Connectivity::Diagonal, false);
+        assert_eq!(
+            graph.neighbours::<HashSet<_>>(&(1, 2)),
+            HashSet::from([(2, 2), (2, 1), (0, 1)])
+        );
+
+        let graph = grid_graph_from_chars(&grid, &passable, Connectivity::Orthogonal, true);
+        assert_eq!(
+            graph.neighbours::<HashSet<_>>(&(1, 2)),
+            HashSet::from([(2, 2), (1, 0)])
+        );
+    }
+
+    #[test]
+    fn weighted_grid() {
+        let grid = vec![vec![1, 9], vec![2, 0]];
+        let weight = |&w: &u32| (w > 0).then_some(w);
+        let graph = weighted_grid_graph(&grid, &weight, Connectivity::Orthogonal, false);
+
+        // Weights are the cost of entering a cell.
+        assert_eq!(graph.weight(&(0, 0), &(0, 1)), 9);
+        assert_eq!(graph.weight(&(0, 1), &(0, 0)), 1);
+        assert!(!graph.contains_node(&(1, 1)));
+    }
+
+    #[test]
+    fn empty_and_ragged_grids() {
+        assert_eq!(grid_graph_2d(0, 3).node_count(), 0);
+        assert_eq!(grid_graph_2d(3, 0).node_count(), 0);
+        assert_eq!(grid_graph_2d(1, 1).nodes::<Vec<_>>(), vec![(0, 0)]);
+        assert_eq!(grid_graph_2d(1, 3).out_degree(&(0, 1)), 2);
+        assert_eq!(grid_graph_3d(0, 2, 2).node_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "equal length")]
+    fn ragged_grid() {
+        let grid = vec![vec!['.'; 3], vec!['.'; 2]];
+        grid_graph_from_chars(&grid, &|_| true, Connectivity::Orthogonal, false);
+    }
+
+    #[test]
+    fn grid_graphs_3d() {
+        let graph = grid_graph_3d(2, 2, 2);
+        assert_eq!(graph.node_count(), 8);
+        assert!(graph.iter().all(|node| graph.out_degree(node) == 3));
+
+        let grid = vec![vec![vec!['.'; 3]; 3]; 3];
+        let graph = weighted_grid_graph_3d(&grid, &|_| Some(1), Connectivity::Diagonal, false);
+        assert_eq!(graph.out_degree(&(1, 1, 1)), 26);
+        assert_eq!(graph.out_degree(&(0, 0, 0)), 7);
+
+        let graph = weighted_grid_graph_3d(&grid, &|_| Some(1), Connectivity::Orthogonal, true);
+        assert!(graph.iter().all(|node| graph.out_degree(node) == 6));
+    }
+}
@@ -1,5 +1,9 @@
 pub mod algorithms;
 mod graph;
+mod grid_graphs;
+mod operations;
 pub use graph::*;
+pub use grid_graphs::*;
+pub use operations::*;
 
 pub use algorithms as algs;
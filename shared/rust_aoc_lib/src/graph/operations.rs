@@ -0,0 +1,359 @@
+use super::algorithms::Traversible;
+use super::{EdgeType, Graph};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+// Subgraphs and set operations
+impl<T, U, D> Graph<T, U, D>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+    D: EdgeType,
+{
+    // Subgraph induced by nodes: all given nodes present in the graph,
+    // together with every edge between them.
+    pub fn subgraph(&self, nodes: &HashSet<T>) -> Self {
+        self.filter(&|node| nodes.contains(node), &|_, _, _| true)
+    }
+
+    // New graph keeping only the nodes and edges accepted by the filters.
+    // An edge is only kept if both of its end nodes are.
+    pub fn filter(
+        &self,
+        node_filter: &dyn Fn(&T) -> bool,
+        edge_filter: &dyn Fn(&T, &T, &U) -> bool,
+    ) -> Self {
+        let mut graph = Self::default();
+        for node in self.iter().filter(|node| node_filter(node)) {
+            graph.add_node(node.clone());
+        }
+        for from in self.iter() {
+            if !graph.contains_node(from) {
+                continue;
+            }
+            for (to, weight) in &self[from] {
+                if graph.contains_node(to) && edge_filter(from, to, weight) {
+                    graph.insert_arc(from.clone(), to.clone(), weight.clone());
+                }
+            }
+        }
+        return graph;
+    }
+
+    // Zero-copy version of filter.
+    pub fn view<'a>(
+        &'a self,
+        node_filter: &'a dyn Fn(&T) -> bool,
+        edge_filter: &'a dyn Fn(&T, &T, &U) -> bool,
+    ) -> GraphView<'a, T, U, D> {
+        GraphView {
+            graph: self,
+            node_filter,
+            edge_filter,
+        }
+    }
+
+    // All nodes and edges of both graphs. Weights of other take precedence.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut graph = self.clone();
+        for node in other.iter() {
+            graph.add_node(node.clone());
+        }
+        for from in other.iter() {
+            for (to, weight) in &other[from] {
+                graph.insert_arc(from.clone(), to.clone(), weight.clone());
+            }
+        }
+        return graph;
+    }
+
+    // Nodes and edges present in both graphs. Weights of self are kept.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.filter(&|node| other.contains_node(node), &|from, to, _| {
+            other.contains_edge(from, to)
+        })
+    }
+
+    // Same nodes, connected exactly where self is not. Self-loops are never added.
+    pub fn complement(&self, weight: U) -> Self {
+        let mut graph = Self::default();
+        for from in self.iter() {
+            graph.add_node(from.clone());
+            for to in self.iter() {
+                if from != to && !self.contains_edge(from, to) {
+                    graph.insert_arc(from.clone(), to.clone(), weight.clone());
+                }
+            }
+        }
+        return graph;
+    }
+
+    // Contract nodes into a single super-node. Edges inside the set are dropped,
+    // and parallel edges created by the contraction are combined with merge.
+    // Panics if into is a node of self outside the set, as its edges would be mixed in.
+    pub fn contract(&self, nodes: &HashSet<T>, into: T, merge: &dyn Fn(&U, &U) -> U) -> Self {
+        assert!(
+            nodes.contains(&into) || !self.contains_node(&into),
+            "Contracted node must be new or one of the contracted nodes."
+        );
+        let rename = |node: &T| {
+            if nodes.contains(node) {
+                into.clone()
+            } else {
+                node.clone()
+            }
+        };
+
+        let mut graph = Self::default();
+        for node in self.iter() {
+            graph.add_node(rename(node));
+        }
+        for from in self.iter() {
+            for (to, weight) in &self[from] {
+                let (from, to) = (rename(from), rename(to));
+                if from == into && to == into {
+                    continue;
+                }
+                let weight = match graph[&from].get(&to) {
+                    Some(existing) => merge(existing, weight),
+                    None => weight.clone(),
+                };
+                graph.insert_arc(from, to, weight);
+            }
+        }
+        return graph;
+    }
+}
+
+impl<T, U> Graph<T, U>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+{
+    // Nodes are the arcs (u, v) of self, connected (u, v) -> (v, w).
+    pub fn line_graph(&self) -> Graph<(T, T), ()> {
+        let mut graph = Graph::new();
+        for u in self.iter() {
+            for v in self[u].keys() {
+                graph.add_node((u.clone(), v.clone()));
+                for w in self[v].keys() {
+                    graph.add_directed_edge((u.clone(), v.clone()), (v.clone(), w.clone()), ());
+                }
+            }
+        }
+        return graph;
+    }
+}
+
+impl<T, U> Graph<T, U, super::Undirected>
+where
+    T: Clone + Hash + Eq + Ord,
+    U: Clone,
+{
+    // Nodes are the edges (u, v) with u <= v, connected when sharing an end node.
+    pub fn line_graph(&self) -> Graph<(T, T), (), super::Undirected> {
+        let edge = |u: &T, v: &T| {
+            if u <= v {
+                (u.clone(), v.clone())
+            } else {
+                (v.clone(), u.clone())
+            }
+        };
+
+        let mut graph = Graph::new_undirected();
+        for u in self.iter() {
+            for v in self[u].keys() {
+                graph.add_node(edge(u, v));
+                for w in self[v].keys() {
+                    if w != u {
+                        graph.add_edge(edge(u, v), edge(v, w), ());
+                    }
+                }
+            }
+        }
+        return graph;
+    }
+}
+
+// A graph seen through node and edge filters, without copying it.
+pub struct GraphView<'a, T, U, D>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+    D: EdgeType,
+{
+    graph: &'a Graph<T, U, D>,
+    node_filter: &'a dyn Fn(&T) -> bool,
+    edge_filter: &'a dyn Fn(&T, &T, &U) -> bool,
+}
+
+impl<T, U, D> GraphView<'_, T, U, D>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+    D: EdgeType,
+{
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.graph.iter().filter(|node| (self.node_filter)(node))
+    }
+
+    pub fn nodes<A: FromIterator<T>>(&self) -> A {
+        self.iter().cloned().collect()
+    }
+
+    pub fn contains_node(&self, node: &T) -> bool {
+        self.graph.contains_node(node) && (self.node_filter)(node)
+    }
+
+    pub fn neighbours<A: FromIterator<T>>(&self, node: &T) -> A {
+        self.edges(node).into_keys().collect()
+    }
+
+    fn edges(&self, node: &T) -> HashMap<T, U> {
+        if !self.contains_node(node) {
+            return HashMap::new();
+        }
+        self.graph[node]
+            .iter()
+            .filter(|(to, weight)| self.contains_node(to) && (self.edge_filter)(node, to, weight))
+            .map(|(to, weight)| (to.clone(), weight.clone()))
+            .collect()
+    }
+
+    // Copy the view into a new graph.
+    pub fn to_graph(&self) -> Graph<T, U, D> {
+        self.graph.filter(self.node_filter, self.edge_filter)
+    }
+}
+
+impl<T, U, D> Traversible<T, U> for GraphView<'_, T, U, D>
+where
+    T: Clone + Hash + Eq,
+    U: Clone,
+    D: EdgeType,
+{
+    fn connections(&self, node: &T) -> HashMap<T, U> {
+        self.edges(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{algs::a_star, grid_graph_2d, UnGraph};
+    use super::*;
+
+    fn path_graph(n: u8) -> UnGraph<u8, u64> {
+        let mut graph = UnGraph::new_undirected();
+        for i in 1..n {
+            graph.add_edge(i - 1, i, 1);
+        }
+        return graph;
+    }
+
+    #[test]
+    fn subgraphs() {
+        let graph = path_graph(5);
+
+        let sub = graph.subgraph(&HashSet::from([1, 2, 4, 7]));
+        assert_eq!(sub.nodes::<HashSet<_>>(), HashSet::from([1, 2, 4]));
+        assert!(sub.contains_edge(&1, &2));
+        assert_eq!(sub.neighbours::<Vec<_>>(&4), vec![]);
+
+        // Keeping one direction of each edge needs the arcs of a directed graph.
+        let graph = graph.to_directed();
+        let no_odd = |node: &u8| node.is_multiple_of(2);
+        let forward = |from: &u8, to: &u8, _: &u64| from < to;
+        let view = graph.view(&no_odd, &forward);
+        assert_eq!(view.nodes::<HashSet<_>>(), HashSet::from([0, 2, 4]));
+        assert_eq!(view.neighbours::<Vec<_>>(&2), vec![]);
+
+        let everything = |_: &u8| true;
+        let view = graph.view(&everything, &forward);
+        assert_eq!(view.neighbours::<Vec<_>>(&2), vec![3]);
+        assert_eq!(view.to_graph().in_degree(&2), 1);
+    }
+
+    #[test]
+    fn view_pathfinding() {
+        let walls = HashSet::from([(0, 1), (1, 1)]);
+        let graph = grid_graph_2d(3, 3);
+        let open = |node: &(usize, usize)| !walls.contains(node);
+        let all = |_: &(usize, usize), _: &(usize, usize), _: &u64| true;
+        let view = graph.view(&open, &all);
+
+        let end = |node: &(usize, usize)| node == &(0, 2);
+        let (length, _) = a_star(&view, &(0, 0), &end, None).unwrap();
+        assert_eq!(length, 6);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = path_graph(3);
+        let mut b = path_graph(2);
+        b.add_edge(0, 2, 5);
+
+        let union = a.union(&b);
+        assert_eq!(union.neighbours::<HashSet<_>>(&0), HashSet::from([1, 2]));
+        assert_eq!(union.weight(&2, &0), 5);
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.nodes::<HashSet<_>>(), HashSet::from([0, 1, 2]));
+        assert_eq!(intersection.neighbours::<Vec<_>>(&2), vec![]);
+        assert_eq!(intersection.neighbours::<Vec<_>>(&0), vec![1]);
+
+        let complement = a.complement(0);
+        assert_eq!(complement.neighbours::<Vec<_>>(&0), vec![2]);
+        assert_eq!(complement.neighbours::<Vec<_>>(&1), vec![]);
+    }
+
+    #[test]
+    fn contraction() {
+        let graph = path_graph(4);
+        let contracted = graph.contract(&HashSet::from([1, 2]), 1, &|a, b| a + b);
+        assert_eq!(contracted.nodes::<HashSet<_>>(), HashSet::from([0, 1, 3]));
+        assert_eq!(
+            contracted.neighbours::<HashSet<_>>(&1),
+            HashSet::from([0, 3])
+        );
+
+        // Parallel edges are merged.
+        let mut graph = path_graph(3);
+        graph.add_edge(0, 2, 1);
+        let contracted = graph.contract(&HashSet::from([1, 2]), 1, &|a, b| a + b);
+        assert_eq!(contracted.weight(&0, &1), 2);
+
+        // A new node for the contraction.
+        let contracted = graph.contract(&HashSet::from([1, 2]), 7, &|a, b| a + b);
+        assert_eq!(contracted.nodes::<HashSet<_>>(), HashSet::from([0, 7]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Contracted node")]
+    fn contraction_into_other_node() {
+        path_graph(4).contract(&HashSet::from([1, 2]), 3, &|a, b| a + b);
+    }
+
+    #[test]
+    fn line_graphs() {
+        let mut graph = Graph::new();
+        graph.add_directed_edge('a', 'b', ());
+        graph.add_directed_edge('b', 'c', ());
+        graph.add_directed_edge('b', 'd', ());
+        let line = graph.line_graph();
+        assert_eq!(line.node_count(), 3);
+        assert_eq!(
+            line.neighbours::<HashSet<_>>(&('a', 'b')),
+            HashSet::from([('b', 'c'), ('b', 'd')])
+        );
+        assert_eq!(line.in_degree(&('a', 'b')), 0);
+
+        // The line graph of a star is a triangle.
+        let mut star = UnGraph::new_undirected();
+        for leaf in 1..4 {
+            star.add_edge(0, leaf, ());
+        }
+        let line = star.line_graph();
+        assert_eq!(line.node_count(), 3);
+        assert!(line.iter().all(|edge| line.degree(edge) == 2));
+    }
+}
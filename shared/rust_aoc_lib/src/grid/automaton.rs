@@ -0,0 +1,310 @@
+use super::{Grid2D, SparseGrid};
+use crate::graph::Connectivity;
+use crate::point::Point2;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Grids a cellular automaton can run on.
+pub trait Cells: Clone + Eq + Hash {
+    type Pos: Copy;
+    type Cell;
+
+    // Positions whose cell may change in the next generation.
+    fn active(&self, connectivity: Connectivity) -> Vec<Self::Pos>;
+    fn neighbours(&self, pos: Self::Pos, connectivity: Connectivity) -> Vec<Self::Pos>;
+    fn cell(&self, pos: Self::Pos) -> &Self::Cell;
+    fn set(&mut self, pos: Self::Pos, value: Self::Cell);
+    // Prepare a buffer from an earlier generation to receive the next one.
+    fn reset_buffer(&self, buffer: &mut Self);
+}
+
+// A dense grid has a fixed size, cells outside it do not exist.
+impl<T: Clone + Eq + Hash> Cells for Grid2D<T> {
+    type Pos = (usize, usize);
+    type Cell = T;
+
+    fn active(&self, _: Connectivity) -> Vec<Self::Pos> {
+        self.positions().collect()
+    }
+
+    fn neighbours(&self, pos: Self::Pos, connectivity: Connectivity) -> Vec<Self::Pos> {
+        match connectivity {
+            Connectivity::Orthogonal => self.neighbours4(pos).collect(),
+            Connectivity::Diagonal => self.neighbours8(pos).collect(),
+        }
+    }
+
+    fn cell(&self, pos: Self::Pos) -> &T {
+        &self[pos]
+    }
+
+    fn set(&mut self, pos: Self::Pos, value: T) {
+        self[pos] = value;
+    }
+
+    fn reset_buffer(&self, _: &mut Self) {
+        // Every cell is overwritten.
+    }
+}
+
+// A sparse grid grows as needed. Cells equal to the default are not stored.
+impl<T: Clone + Eq + Hash> Cells for SparseGrid<T> {
+    type Pos = Point2<i64>;
+    type Cell = T;
+
+    fn active(&self, connectivity: Connectivity) -> Vec<Self::Pos> {
+        let mut positions: Vec<Self::Pos> = self.positions().collect();
+        for pos in self.positions() {
+            positions.extend(self.neighbours(pos, connectivity));
+        }
+        positions.sort_by_key(|pos| (pos.y, pos.x));
+        positions.dedup();
+        return positions;
+    }
+
+    fn neighbours(&self, pos: Self::Pos, connectivity: Connectivity) -> Vec<Self::Pos> {
+        let mut result = Vec::new();
+        for dy in -1_i64..=1 {
+            for dx in -1..=1 {
+                let orthogonal = dx == 0 || dy == 0;
+                if (dx, dy) != (0, 0) && (orthogonal || connectivity == Connectivity::Diagonal) {
+                    result.push(pos + Point2::new(dx, dy));
+                }
+            }
+        }
+        return result;
+    }
+
+    fn cell(&self, pos: Self::Pos) -> &T {
+        self.get(pos)
+    }
+
+    fn set(&mut self, pos: Self::Pos, value: T) {
+        if value == self.default {
+            self.remove(pos);
+        } else {
+            self.insert(pos, value);
+        }
+    }
+
+    fn reset_buffer(&self, buffer: &mut Self) {
+        buffer.clear();
+        buffer.default = self.default.clone();
+    }
+}
+
+// How the next value of a cell is computed.
+pub enum Rule<'a, T> {
+    // From the cell and the number of its neighbours that satisfy the predicate.
+    Count(&'a dyn Fn(&T) -> bool, &'a dyn Fn(&T, usize) -> T),
+    // From the cell and all its neighbours.
+    Neighbourhood(&'a dyn Fn(&T, &[&T]) -> T),
+}
+
+pub struct Automaton<'a, G: Cells> {
+    pub grid: G,
+    pub generation: usize,
+    pub connectivity: Connectivity,
+    rule: Rule<'a, G::Cell>,
+    // The previous generation, reused to store the next.
+    buffer: Option<G>,
+}
+
+impl<'a, G: Cells> Automaton<'a, G> {
+    pub fn new(grid: G, connectivity: Connectivity, rule: Rule<'a, G::Cell>) -> Self {
+        Self {
+            grid,
+            generation: 0,
+            connectivity,
+            rule,
+            buffer: None,
+        }
+    }
+
+    pub fn step(&mut self) {
+        let mut next = self.buffer.take().unwrap_or_else(|| self.grid.clone());
+        self.grid.reset_buffer(&mut next);
+        for pos in self.grid.active(self.connectivity) {
+            let neighbours = self.grid.neighbours(pos, self.connectivity);
+            let cell = self.grid.cell(pos);
+            let value = match &self.rule {
+                Rule::Count(alive, rule) => {
+                    let count = neighbours
+                        .into_iter()
+                        .filter(|&n| alive(self.grid.cell(n)))
+                        .count();
+                    rule(cell, count)
+                }
+                Rule::Neighbourhood(rule) => {
+                    let cells: Vec<&G::Cell> =
+                        neighbours.into_iter().map(|n| self.grid.cell(n)).collect();
+                    rule(cell, &cells)
+                }
+            };
+            next.set(pos, value);
+        }
+        self.buffer = Some(std::mem::replace(&mut self.grid, next));
+        self.generation += 1;
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    // Advance to generation n, skipping ahead once a state repeats.
+    // Returns the cycle (first generation, period) if one was found.
+    pub fn run_to(&mut self, n: usize) -> Option<(usize, usize)> {
+        let mut seen = HashMap::from([(self.grid.clone(), self.generation)]);
+        while self.generation < n {
+            self.step();
+            if let Some(&first) = seen.get(&self.grid) {
+                let period = self.generation - first;
+                self.run((n - self.generation) % period);
+                self.generation = n;
+                return Some((first, period));
+            }
+            seen.insert(self.grid.clone(), self.generation);
+        }
+        return None;
+    }
+}
+
+// The state after n steps from start, skipping ahead once a state repeats.
+pub fn nth_state<S: Clone + Eq + Hash>(start: S, n: usize, step: &dyn Fn(&S) -> S) -> S {
+    let mut history = vec![start.clone()];
+    let mut seen = HashMap::from([(start, 0)]);
+    for k in 1..=n {
+        let state = step(&history[k - 1]);
+        if let Some(&first) = seen.get(&state) {
+            let period = k - first;
+            return history[first + (n - first) % period].clone();
+        }
+        seen.insert(state.clone(), k);
+        history.push(state);
+    }
+    return history.pop().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{roll, rotate};
+    use super::*;
+    use std::collections::HashSet;
+
+    // Conway's game of life on '#' and '.'.
+    fn life(cell: &char, count: usize) -> char {
+        match (cell, count) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn blinker() {
+        let grid = Grid2D::parse(".....\n..#..\n..#..\n..#..\n.....");
+        let vertical = grid.to_string();
+        let mut automaton = Automaton::new(
+            grid,
+            Connectivity::Diagonal,
+            Rule::Count(&|&c| c == '#', &life),
+        );
+
+        automaton.step();
+        let horizontal = ".....\n.....\n.###.\n.....\n.....";
+        assert_eq!(automaton.grid.to_string(), horizontal);
+
+        assert_eq!(automaton.run_to(1_000_001), Some((1, 2)));
+        assert_eq!(automaton.generation, 1_000_001);
+        assert_eq!(automaton.grid.to_string(), horizontal);
+        automaton.step();
+        assert_eq!(automaton.grid.to_string(), vertical);
+    }
+
+    #[test]
+    fn glider() {
+        let grid = SparseGrid::parse_with(".#.\n..#\n###", '.', &|c| (c == '#').then_some(c));
+        let start: HashSet<Point2<i64>> = grid.positions().collect();
+        let mut automaton = Automaton::new(
+            grid,
+            Connectivity::Diagonal,
+            Rule::Count(&|&c| c == '#', &life),
+        );
+
+        // Every four generations the glider moves one cell down and right.
+        automaton.run(40);
+        let moved: HashSet<Point2<i64>> = automaton.grid.positions().collect();
+        let expected = start.iter().map(|&p| p + Point2::new(10, 10)).collect();
+        assert_eq!(moved, expected);
+        assert_eq!(automaton.grid.size(), (3, 3));
+    }
+
+    #[test]
+    fn expanding_plots() {
+        // Example from year 2023 day 21, marking the reachable plots with 'O'.
+        let grid = Grid2D::parse(
+            "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........",
+        )
+        .map(&|&c| if c == 'S' { 'O' } else { c });
+        let spread = |cell: &char, neighbours: &[&char]| match cell {
+            '#' => '#',
+            _ if neighbours.contains(&&'O') => 'O',
+            _ => '.',
+        };
+        let mut automaton =
+            Automaton::new(grid, Connectivity::Orthogonal, Rule::Neighbourhood(&spread));
+
+        automaton.run(6);
+        assert_eq!(automaton.grid.find_all(&'O').len(), 16);
+    }
+
+    #[test]
+    fn spin_cycles() {
+        // Example from year 2023 day 14.
+        let platform: Vec<Vec<char>> = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#...."
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let spin = |grid: &Vec<Vec<char>>| {
+            let mut grid = grid.clone();
+            for _ in 0..4 {
+                // Tilt north, then turn such that west becomes north.
+                roll(&mut grid, (-1, 0), &'O', &'.');
+                grid = rotate(&grid, 1);
+            }
+            grid
+        };
+        let load = |grid: &Vec<Vec<char>>| -> usize {
+            let n = grid.len();
+            (0..n)
+                .map(|i| (n - i) * grid[i].iter().filter(|&&c| c == 'O').count())
+                .sum()
+        };
+
+        assert_eq!(load(&nth_state(platform.clone(), 3, &spin)), 69);
+        assert_eq!(load(&nth_state(platform, 1_000_000_000, &spin)), 64);
+    }
+}
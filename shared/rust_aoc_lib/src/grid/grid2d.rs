@@ -0,0 +1,299 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+// Offsets (di, dj) of the orthogonal neighbours followed by the diagonal ones.
+const OFFSETS: [(i64, i64); 8] = [
+    (-1, 0),
+    (0, 1),
+    (1, 0),
+    (0, -1),
+    (-1, 1),
+    (1, 1),
+    (1, -1),
+    (-1, -1),
+];
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid2D<T> {
+    // Cells stored in row-major order: cell (i, j) is data[i * cols + j].
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid2D<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    // Panics if the rows are not all of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "Grid rows must have equal length."
+        );
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    // Parse a text map with one row per line, converting each character. Lines are
+    // kept as they are, so blank lines or trailing spaces give rows of other lengths,
+    // which are an error.
+    pub fn parse_with(input: &str, convert: &dyn Fn(char) -> T) -> Result<Self, String> {
+        let rows: Vec<Vec<T>> = input
+            .lines()
+            .map(|line| line.chars().map(convert).collect())
+            .collect();
+        let cols = rows.first().map_or(0, |row| row.len());
+        if let Some(i) = rows.iter().position(|row| row.len() != cols) {
+            return Err(format!(
+                "Row {i} has length {} instead of {cols}.",
+                rows[i].len()
+            ));
+        }
+        Ok(Self::from_rows(rows))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // All cells in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        (0..self.rows).map(|i| self.row(i).to_vec()).collect()
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    // Is the signed position (i, j) inside the grid.
+    pub fn contains(&self, (i, j): (i64, i64)) -> bool {
+        0 <= i && i < self.rows as i64 && 0 <= j && j < self.cols as i64
+    }
+
+    // Bounds-checked signed indexing.
+    pub fn get(&self, pos: (i64, i64)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.data[pos.0 as usize * self.cols + pos.1 as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: (i64, i64)) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.data[pos.0 as usize * self.cols + pos.1 as usize])
+        } else {
+            None
+        }
+    }
+
+    // Iterate through ((i, j), cell) in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(k, value)| ((k / cols, k % cols), value))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |k| (k / cols, k % cols))
+    }
+
+    // First cell in reading order equal to value.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, x)| x == &value).map(|(pos, _)| pos)
+    }
+
+    pub fn find_all(&self, value: &T) -> Vec<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(|(_, x)| x == &value)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    // The up to 4 orthogonal neighbours of pos inside the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(pos, &OFFSETS[..4])
+    }
+
+    // The up to 8 orthogonal and diagonal neighbours of pos inside the grid.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(pos, &OFFSETS)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        (i, j): (usize, usize),
+        offsets: &'a [(i64, i64)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .map(move |(di, dj)| (i as i64 + di, j as i64 + dj))
+            .filter(|&pos| self.contains(pos))
+            .map(|(i, j)| (i as usize, j as usize))
+    }
+
+    pub fn map<V>(&self, fun: &dyn Fn(&T) -> V) -> Grid2D<V> {
+        Grid2D {
+            data: self.data.iter().map(fun).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl Grid2D<char> {
+    // Panics if the rows are not all of equal length.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, &|c| c).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl FromStr for Grid2D<char> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &|c| c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({i}, {j}) out of bounds."
+        );
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    #[inline(always)]
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({i}, {j}) out of bounds."
+        );
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T> Display for Grid2D<T>
+where
+    T: Display,
+{
+    // Renders one line per row, such that a parsed char grid displays as its input.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.rows {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for value in self.row(i) {
+                write!(f, "{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const MAP: &str = "#.#\n.S.\n..#\n";
+
+    #[test]
+    fn parsing() {
+        let grid = Grid2D::parse(MAP);
+        assert_eq!((grid.rows(), grid.cols()), (3, 3));
+        assert_eq!(grid.as_slice()[4], 'S');
+        assert_eq!(grid[(1, 1)], 'S');
+        assert_eq!(grid.to_string(), MAP.trim_end());
+        assert_eq!(MAP.parse::<Grid2D<char>>().unwrap(), grid);
+
+        let digits = Grid2D::parse_with("12\n34", &|c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+        assert_eq!(digits.to_string(), "12\n34");
+
+        // Ragged rows are not padded or trimmed.
+        assert!("#.#\n.S\n..#".parse::<Grid2D<char>>().is_err());
+        assert!("#.#\n.S. \n..#".parse::<Grid2D<char>>().is_err());
+        assert!("#.#\n\n..#".parse::<Grid2D<char>>().is_err());
+        assert_eq!(
+            Grid2D::parse_with("12\n3", &|c| c),
+            Err("Row 1 has length 1 instead of 2.".to_string())
+        );
+    }
+
+    #[test]
+    fn indexing() {
+        let mut grid = Grid2D::parse(MAP);
+        assert_eq!(grid.get((0, 1)), Some(&'.'));
+        assert_eq!(grid.get((-1, 1)), None);
+        assert_eq!(grid.get((1, 3)), None);
+
+        *grid.get_mut((2, 0)).unwrap() = 'x';
+        grid[(0, 1)] = 'y';
+        assert_eq!(grid.to_string(), "#y#\n.S.\nx.#");
+    }
+
+    #[test]
+    fn searching() {
+        let grid = Grid2D::parse(MAP);
+        assert_eq!(grid.find(&'S'), Some((1, 1)));
+        assert_eq!(grid.find(&'?'), None);
+        assert_eq!(grid.find_all(&'#'), vec![(0, 0), (0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid2D::new(3, 4, 0);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours4((0, 3)).collect::<HashSet<_>>(),
+            HashSet::from([(1, 3), (0, 2)])
+        );
+        assert_eq!(
+            grid.neighbours8((0, 3)).collect::<HashSet<_>>(),
+            HashSet::from([(1, 3), (0, 2), (1, 2)])
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use super::{Cuboid, FACES};
+use crate::point::Point3;
+use std::collections::HashSet;
+use std::ops::{Index, IndexMut};
+
+// A dense box of voxels covering the cells of bounds.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid3D<T> {
+    // Cells stored with x varying fastest, then y, then z.
+    pub data: Vec<T>,
+    pub bounds: Cuboid,
+}
+
+impl<T> Grid3D<T> {
+    pub fn new(bounds: Cuboid, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; bounds.volume() as usize],
+            bounds,
+        }
+    }
+
+    // Voxel grid over the bounding box of cells, holding inside for the cells and outside elsewhere.
+    // Returns None if there are no cells.
+    pub fn from_cells(cells: &HashSet<Point3<i64>>, inside: T, outside: T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        let bounds = Cuboid::bounding(cells.iter().copied())?;
+        let mut grid = Self::new(bounds, outside);
+        for &p in cells {
+            grid[p] = inside.clone();
+        }
+        return Some(grid);
+    }
+
+    fn offset(&self, p: Point3<i64>) -> usize {
+        let (size, q) = (self.bounds.size(), p - self.bounds.min);
+        ((q.z * size.y + q.y) * size.x + q.x) as usize
+    }
+
+    pub fn contains(&self, p: Point3<i64>) -> bool {
+        self.bounds.contains(p)
+    }
+
+    pub fn get(&self, p: Point3<i64>) -> Option<&T> {
+        self.contains(p).then(|| &self.data[self.offset(p)])
+    }
+
+    pub fn get_mut(&mut self, p: Point3<i64>) -> Option<&mut T> {
+        if !self.contains(p) {
+            return None;
+        }
+        let offset = self.offset(p);
+        return Some(&mut self.data[offset]);
+    }
+
+    // Iterate through (position, cell) with x varying fastest.
+    pub fn iter(&self) -> impl Iterator<Item = (Point3<i64>, &T)> {
+        self.bounds.cells().zip(&self.data)
+    }
+
+    // Fill every cell of the box that lies inside the grid.
+    pub fn fill(&mut self, cuboid: &Cuboid, value: T)
+    where
+        T: Clone,
+    {
+        if let Some(overlap) = self.bounds.intersection(cuboid) {
+            for p in overlap.cells() {
+                self[p] = value.clone();
+            }
+        }
+    }
+
+    // The up to 6 face-adjacent neighbours of p inside the grid.
+    pub fn neighbours6(&self, p: Point3<i64>) -> impl Iterator<Item = Point3<i64>> + '_ {
+        FACES
+            .iter()
+            .map(move |&d| p + d)
+            .filter(|&q| self.contains(q))
+    }
+}
+
+// Panics if p lies outside the grid.
+impl<T> Index<Point3<i64>> for Grid3D<T> {
+    type Output = T;
+
+    fn index(&self, p: Point3<i64>) -> &T {
+        self.get(p).expect("Position outside the grid.")
+    }
+}
+
+impl<T> IndexMut<Point3<i64>> for Grid3D<T> {
+    fn index_mut(&mut self, p: Point3<i64>) -> &mut T {
+        self.get_mut(p).expect("Position outside the grid.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voxels() {
+        let bounds = Cuboid::new(Point3::new(-1, -1, -1), Point3::new(1, 2, 3));
+        let mut grid = Grid3D::new(bounds, 0);
+        assert_eq!(grid.data.len(), 60);
+
+        grid.fill(&"0,0,0~5,5,0".parse().unwrap(), 1);
+        assert_eq!(grid.iter().filter(|(_, &v)| v == 1).count(), 6);
+        assert_eq!(grid[Point3::new(1, 2, 0)], 1);
+        assert_eq!(grid.get(Point3::new(2, 0, 0)), None);
+        let far = Point3::new(0, 0, i64::MAX / 2);
+        assert_eq!(grid.get(far), None);
+        assert_eq!(grid.get_mut(far), None);
+
+        grid[Point3::new(-1, -1, -1)] = 7;
+        assert_eq!(grid.data[0], 7);
+        assert_eq!(grid.neighbours6(Point3::new(-1, -1, -1)).count(), 3);
+        assert_eq!(grid.neighbours6(Point3::new(0, 0, 0)).count(), 6);
+
+        let cells = HashSet::from([Point3::new(0, 0, 0), Point3::new(2, 1, 0)]);
+        let grid = Grid3D::from_cells(&cells, '#', '.').unwrap();
+        assert_eq!(grid.bounds.size(), Point3::new(3, 2, 1));
+        assert_eq!(grid.data.iter().collect::<String>(), "#....#");
+    }
+}
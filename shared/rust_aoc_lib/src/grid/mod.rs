@@ -0,0 +1,21 @@
+mod automaton;
+mod grid2d;
+mod grid3d;
+mod paths;
+mod rays;
+mod regions;
+mod sparse;
+mod tiled;
+mod transform;
+mod voxel;
+
+pub use automaton::*;
+pub use grid2d::*;
+pub use grid3d::*;
+pub use paths::*;
+pub use rays::*;
+pub use regions::*;
+pub use sparse::*;
+pub use tiled::*;
+pub use transform::*;
+pub use voxel::*;
@@ -0,0 +1,297 @@
+// Shortest paths on character maps stored as rows, grid[i][j], moving between
+// orthogonal neighbours. Unit-step versions take a passability predicate, weighted
+// versions take the cost of entering a cell, None for walls.
+
+use crate::graph::algs::{a_star, distances, Traversible};
+use crate::num::Num;
+use crate::point::Direction;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+type Pos = (usize, usize);
+
+// A position together with the direction faced.
+pub type Heading = (Pos, Direction);
+
+pub fn manhattan(a: Pos, b: Pos) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+fn contains(grid: &[Vec<char>], (i, j): Pos) -> bool {
+    i < grid.len() && j < grid[i].len()
+}
+
+fn step(grid: &[Vec<char>], (i, j): Pos, dir: Direction) -> Option<Pos> {
+    let (di, dj) = dir.grid_step();
+    let next = (
+        i.checked_add_signed(di as isize)?,
+        j.checked_add_signed(dj as isize)?,
+    );
+    contains(grid, next).then_some(next)
+}
+
+// Walking between orthogonal neighbours, paying the cost of the cell entered.
+struct CellWalk<'a, U> {
+    grid: &'a [Vec<char>],
+    cost: &'a dyn Fn(char) -> Option<U>,
+}
+
+impl<U> Traversible<Pos, U> for CellWalk<'_, U> {
+    fn connections(&self, &pos: &Pos) -> HashMap<Pos, U> {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(|dir| {
+                let (i, j) = step(self.grid, pos, dir)?;
+                Some(((i, j), (self.cost)(self.grid[i][j])?))
+            })
+            .collect()
+    }
+}
+
+// Moving forward pays the cost of the cell entered, turning 90 degrees in place pays turn.
+struct HeadingWalk<'a, U> {
+    grid: &'a [Vec<char>],
+    cost: &'a dyn Fn(char) -> Option<U>,
+    turn: U,
+}
+
+impl<U: Copy> Traversible<Heading, U> for HeadingWalk<'_, U> {
+    fn connections(&self, &(pos, dir): &Heading) -> HashMap<Heading, U> {
+        let mut result = HashMap::from([
+            ((pos, dir.turn_left()), self.turn),
+            ((pos, dir.turn_right()), self.turn),
+        ]);
+        if let Some((i, j)) = step(self.grid, pos, dir) {
+            if let Some(cost) = (self.cost)(self.grid[i][j]) {
+                result.insert(((i, j), dir), cost);
+            }
+        }
+        return result;
+    }
+}
+
+// Multiples k * c for k = 0..=grid.rows() + grid.cols(), where c is the cheapest cell.
+// Manhattan distance times c is an admissible heuristic for weighted walks.
+fn heuristic_table<U: Num>(grid: &[Vec<char>], cost: &dyn Fn(char) -> Option<U>) -> Vec<U> {
+    let cheapest = grid
+        .iter()
+        .flatten()
+        .filter_map(|&c| cost(c))
+        .fold(None, |min: Option<U>, c| match min {
+            Some(m) if m <= c => Some(m),
+            _ => Some(c),
+        })
+        .unwrap_or(U::ZERO);
+    let n = grid.len() + grid.first().map_or(0, |row| row.len());
+    let mut table = vec![U::ZERO];
+    for k in 1..=n {
+        table.push(table[k - 1].saturating_add(cheapest));
+    }
+    return table;
+}
+
+// Lower bound on the cost from pos to goal. Clamping to the table keeps it admissible
+// for rows longer than the first.
+fn lower_bound<U: Copy>(table: &[U], pos: Pos, goal: Pos) -> U {
+    table[manhattan(pos, goal).min(table.len() - 1)]
+}
+
+// Fewest unit steps from start to goal, and the cells along the way.
+pub fn shortest_path(
+    grid: &[Vec<char>],
+    start: Pos,
+    goal: Pos,
+    passable: &dyn Fn(char) -> bool,
+) -> Option<(usize, Vec<Pos>)> {
+    let cost = |c| passable(c).then_some(1);
+    shortest_path_weighted(grid, start, goal, &cost)
+}
+
+// Cheapest path from start to goal, where entering a cell costs cost(cell).
+pub fn shortest_path_weighted<U: Num>(
+    grid: &[Vec<char>],
+    start: Pos,
+    goal: Pos,
+    cost: &dyn Fn(char) -> Option<U>,
+) -> Option<(U, Vec<Pos>)> {
+    if !contains(grid, goal) {
+        return None;
+    }
+    let table = heuristic_table(grid, cost);
+    let heuristic = |&pos: &Pos| lower_bound(&table, pos, goal);
+    let walk = CellWalk { grid, cost };
+    a_star(&walk, &start, &|&pos| pos == goal, Some(&heuristic))
+}
+
+// Cheapest path from the start heading to goal facing any direction, where moving
+// forward costs cost(cell entered) and each quarter turn in place costs turn.
+pub fn shortest_path_turning<U: Num>(
+    grid: &[Vec<char>],
+    start: Heading,
+    goal: Pos,
+    cost: &dyn Fn(char) -> Option<U>,
+    turn: U,
+) -> Option<(U, Vec<Heading>)> {
+    if !contains(grid, goal) {
+        return None;
+    }
+    let table = heuristic_table(grid, cost);
+    let heuristic = |&(pos, _): &Heading| lower_bound(&table, pos, goal);
+    let walk = HeadingWalk { grid, cost, turn };
+    a_star(&walk, &start, &|&(pos, _)| pos == goal, Some(&heuristic))
+}
+
+// Fewest unit steps from start to every reachable cell.
+pub fn distance_map(
+    grid: &[Vec<char>],
+    start: Pos,
+    passable: &dyn Fn(char) -> bool,
+) -> HashMap<Pos, usize> {
+    let mut dist = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(pos) = queue.pop_front() {
+        for dir in Direction::ORTHOGONAL {
+            if let Some((i, j)) = step(grid, pos, dir) {
+                if passable(grid[i][j]) && !dist.contains_key(&(i, j)) {
+                    dist.insert((i, j), dist[&pos] + 1);
+                    queue.push_back((i, j));
+                }
+            }
+        }
+    }
+    return dist;
+}
+
+// Cheapest cost from start to every reachable cell.
+pub fn distance_map_weighted<U: Num>(
+    grid: &[Vec<char>],
+    start: Pos,
+    cost: &dyn Fn(char) -> Option<U>,
+) -> HashMap<Pos, U> {
+    distances(&CellWalk { grid, cost }, &[start])
+}
+
+// Cheapest cost from the nearest of starts to every reachable heading.
+pub fn distance_map_turning<U: Num>(
+    grid: &[Vec<char>],
+    starts: &[Heading],
+    cost: &dyn Fn(char) -> Option<U>,
+    turn: U,
+) -> HashMap<Heading, U> {
+    distances(&HeadingWalk { grid, cost, turn }, starts)
+}
+
+// Cells reachable from start, including start itself.
+pub fn reachable(grid: &[Vec<char>], start: Pos, passable: &dyn Fn(char) -> bool) -> HashSet<Pos> {
+    distance_map(grid, start, passable).into_keys().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<Vec<char>> {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    fn find(grid: &[Vec<char>], target: char) -> Pos {
+        (0..grid.len())
+            .flat_map(|i| (0..grid[i].len()).map(move |j| (i, j)))
+            .find(|&(i, j)| grid[i][j] == target)
+            .unwrap()
+    }
+
+    #[test]
+    fn maze() {
+        let grid = parse("...#.\n.#...\n.#.#.\n.#.#.\n...#.");
+        let open = |c| c != '#';
+
+        let (steps, path) = shortest_path(&grid, (0, 0), (4, 4), &open).unwrap();
+        assert_eq!(steps, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path[3], (1, 2));
+
+        assert_eq!(shortest_path(&grid, (0, 0), (10, 10), &open), None);
+        let ragged = parse("..\n.....\n........");
+        assert_eq!(shortest_path(&ragged, (0, 0), (2, 7), &open).unwrap().0, 9);
+
+        let dist = distance_map(&grid, (0, 0), &open);
+        assert_eq!(dist[&(4, 4)], 8);
+        assert_eq!(dist[&(4, 2)], 6);
+        assert_eq!(reachable(&grid, (0, 0), &open).len(), 18);
+
+        // Walling off the goal.
+        let blocked = parse("...#.\n.#.##\n.#.#.\n.#.#.\n...#.");
+        assert_eq!(shortest_path(&blocked, (0, 0), (4, 4), &open), None);
+        assert!(!reachable(&blocked, (0, 0), &open).contains(&(4, 4)));
+    }
+
+    #[test]
+    fn weighted() {
+        // Example from year 2023 day 17, ignoring the limits on straight moves.
+        let grid = parse("2413\n3215\n3255\n3446");
+        let cost = |c: char| c.to_digit(10);
+
+        let (total, path) = shortest_path_weighted(&grid, (0, 0), (3, 3), &cost).unwrap();
+        let dist = distance_map_weighted(&grid, (0, 0), &cost);
+        assert_eq!(dist[&(3, 3)], total);
+        assert_eq!(
+            total,
+            path[1..]
+                .iter()
+                .map(|&(i, j)| cost(grid[i][j]).unwrap())
+                .sum()
+        );
+        assert_eq!(total, 21);
+    }
+
+    #[test]
+    fn reindeer_maze() {
+        // Example from year 2024 day 16.
+        let grid = parse(
+            "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############",
+        );
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+        let cost = |c| (c != '#').then_some(1u64);
+
+        let (score, path) =
+            shortest_path_turning(&grid, (start, Direction::E), end, &cost, 1000).unwrap();
+        assert_eq!(score, 7036);
+        assert_eq!(path.last().unwrap().0, end);
+        let outside = (grid.len(), 0);
+        assert_eq!(
+            shortest_path_turning(&grid, (start, Direction::E), outside, &cost, 1000),
+            None
+        );
+
+        // Tiles on any best path: the distances from start and back from end add up to the best score.
+        let forward = distance_map_turning(&grid, &[(start, Direction::E)], &cost, 1000);
+        let ends: Vec<Heading> = Direction::ORTHOGONAL
+            .iter()
+            .map(|&dir| (end, dir))
+            .collect();
+        let backward = distance_map_turning(&grid, &ends, &cost, 1000);
+        let tiles: HashSet<Pos> = forward
+            .iter()
+            .filter(|&(&(pos, dir), d)| {
+                backward.get(&(pos, dir.reverse())).map(|b| d + b) == Some(score)
+            })
+            .map(|(&(pos, _), _)| pos)
+            .collect();
+        assert_eq!(tiles.len(), 45);
+    }
+}
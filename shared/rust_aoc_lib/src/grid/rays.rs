@@ -0,0 +1,134 @@
+use super::Grid2D;
+use std::collections::HashSet;
+
+// Unit step (di, dj) in which a beam moves.
+type Step = (i64, i64);
+
+// A beam is at a cell, moving in the direction of a step.
+pub type BeamState = ((usize, usize), Step);
+
+// Result of tracing beams through a grid.
+#[derive(Debug, Clone)]
+pub struct Beams {
+    // Cells visited by at least one beam.
+    pub energised: HashSet<(usize, usize)>,
+    // Every (cell, incoming direction) visited.
+    pub states: HashSet<BeamState>,
+    // Whether a beam ever re-entered a visited state. For a single beam this
+    // means that it would walk in a loop forever.
+    pub looped: bool,
+}
+
+// Propagate beams entering start = (cell, direction), where each cell sends a beam
+// onwards in the directions rule(cell value, incoming direction). Beams end when
+// they leave the grid, or when the rule returns no directions.
+pub fn trace_beams<T>(
+    grid: &Grid2D<T>,
+    start: BeamState,
+    rule: &dyn Fn(&T, Step) -> Vec<Step>,
+) -> Beams {
+    trace_beams_with(grid, start, &|grid, (pos, dir)| rule(&grid[pos], dir))
+}
+
+// As trace_beams, but the rule may inspect the whole grid around the beam,
+// e.g. to turn before walking into an obstacle.
+pub fn trace_beams_with<T>(
+    grid: &Grid2D<T>,
+    start: BeamState,
+    rule: &dyn Fn(&Grid2D<T>, BeamState) -> Vec<Step>,
+) -> Beams {
+    let mut states = HashSet::from([start]);
+    let mut looped = false;
+    let mut stack = vec![start];
+    while let Some((pos, dir)) = stack.pop() {
+        for out in rule(grid, (pos, dir)) {
+            let next = (pos.0 as i64 + out.0, pos.1 as i64 + out.1);
+            if !grid.contains(next) {
+                continue;
+            }
+            let state = ((next.0 as usize, next.1 as usize), out);
+            if states.insert(state) {
+                stack.push(state);
+            } else {
+                looped = true;
+            }
+        }
+    }
+
+    return Beams {
+        energised: states.iter().map(|&(pos, _)| pos).collect(),
+        states,
+        looped,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirrors(cell: &char, (di, dj): (i64, i64)) -> Vec<(i64, i64)> {
+        match cell {
+            '/' => vec![(-dj, -di)],
+            '\\' => vec![(dj, di)],
+            '|' if dj != 0 => vec![(-1, 0), (1, 0)],
+            '-' if di != 0 => vec![(0, -1), (0, 1)],
+            _ => vec![(di, dj)],
+        }
+    }
+
+    #[test]
+    fn light_beams() {
+        // Example from year 2023 day 16.
+        let grid = Grid2D::parse(
+            r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....",
+        );
+
+        let beams = trace_beams(&grid, ((0, 0), (0, 1)), &mirrors);
+        assert_eq!(beams.energised.len(), 46);
+
+        let beams = trace_beams(&grid, ((0, 3), (1, 0)), &mirrors);
+        assert_eq!(beams.energised.len(), 51);
+    }
+
+    #[test]
+    fn guard_walk() {
+        // Example from year 2024 day 6.
+        let mut grid = Grid2D::parse(
+            "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...",
+        );
+        let start = (grid.find(&'^').unwrap(), (-1, 0));
+
+        // Turn right until the cell ahead is free.
+        let guard = |grid: &Grid2D<char>, ((i, j), (mut di, mut dj)): BeamState| {
+            while grid.get((i as i64 + di, j as i64 + dj)) == Some(&'#') {
+                (di, dj) = (dj, -di);
+            }
+            vec![(di, dj)]
+        };
+
+        let beams = trace_beams_with(&grid, start, &guard);
+        assert_eq!(beams.energised.len(), 41);
+        assert!(!beams.looped);
+
+        grid[(6, 3)] = '#';
+        assert!(trace_beams_with(&grid, start, &guard).looped);
+    }
+}
@@ -0,0 +1,201 @@
+use super::Grid2D;
+use std::collections::HashSet;
+
+// Orthogonal unit steps (di, dj), such that each is a clockwise turn of the previous.
+const STEPS: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+// A 4-connected region of cells and its shape descriptors.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Region {
+    pub label: usize,
+    // Cells of the region in reading order.
+    pub cells: Vec<(usize, usize)>,
+    pub area: usize,
+    // Number of unit cell sides on the boundary.
+    pub perimeter: usize,
+    // Number of straight boundary segments, which equals the number of corners.
+    pub sides: usize,
+    // Inclusive ((min_i, min_j), (max_i, max_j)).
+    pub bounding_box: ((usize, usize), (usize, usize)),
+    // Number of enclosed areas not part of the region.
+    pub holes: usize,
+}
+
+// Label 4-connected regions where neighbouring cells a, b belong together if same(a, b).
+// Returns a grid of labels 0..n and the number of regions n.
+pub fn label_regions<T>(grid: &Grid2D<T>, same: &dyn Fn(&T, &T) -> bool) -> (Grid2D<usize>, usize) {
+    let mut labels = Grid2D::new(grid.rows(), grid.cols(), usize::MAX);
+    let mut count = 0;
+    for start in grid.positions() {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+        // Flood fill from start.
+        labels[start] = count;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for next in grid.neighbours4(pos) {
+                if labels[next] == usize::MAX && same(&grid[pos], &grid[next]) {
+                    labels[next] = count;
+                    stack.push(next);
+                }
+            }
+        }
+        count += 1;
+    }
+    return (labels, count);
+}
+
+// Regions of equal cells.
+pub fn regions<T: PartialEq>(grid: &Grid2D<T>) -> Vec<Region> {
+    regions_by(grid, &|a, b| a == b)
+}
+
+// Regions of cells connected under a custom predicate.
+pub fn regions_by<T>(grid: &Grid2D<T>, same: &dyn Fn(&T, &T) -> bool) -> Vec<Region> {
+    let (labels, count) = label_regions(grid, same);
+    let mut cells = vec![Vec::new(); count];
+    for (pos, &label) in labels.iter() {
+        cells[label].push(pos);
+    }
+    cells
+        .into_iter()
+        .enumerate()
+        .map(|(label, cells)| describe_region(&labels, label, cells))
+        .collect()
+}
+
+fn describe_region(labels: &Grid2D<usize>, label: usize, cells: Vec<(usize, usize)>) -> Region {
+    let inside = |(i, j): (i64, i64)| labels.get((i, j)) == Some(&label);
+
+    let (mut perimeter, mut sides) = (0, 0);
+    for &(i, j) in &cells {
+        let pos = (i as i64, j as i64);
+        for (k, &normal) in STEPS.iter().enumerate() {
+            let tangent = STEPS[(k + 1) % 4];
+            let outside = |step: (i64, i64)| !inside((pos.0 + step.0, pos.1 + step.1));
+            let diagonal = (normal.0 + tangent.0, normal.1 + tangent.1);
+
+            if outside(normal) {
+                perimeter += 1;
+                // Count each side once: at the end where it stops continuing along tangent.
+                if outside(tangent) || !outside(diagonal) {
+                    sides += 1;
+                }
+            }
+        }
+    }
+
+    let min_i = cells.iter().map(|pos| pos.0).min().unwrap();
+    let max_i = cells.iter().map(|pos| pos.0).max().unwrap();
+    let min_j = cells.iter().map(|pos| pos.1).min().unwrap();
+    let max_j = cells.iter().map(|pos| pos.1).max().unwrap();
+
+    return Region {
+        label,
+        area: cells.len(),
+        holes: count_holes(&inside, (min_i, min_j), (max_i, max_j)),
+        cells,
+        perimeter,
+        sides,
+        bounding_box: ((min_i, min_j), (max_i, max_j)),
+    };
+}
+
+fn count_holes(
+    inside: &dyn Fn((i64, i64)) -> bool,
+    min: (usize, usize),
+    max: (usize, usize),
+) -> usize {
+    // Flood fill the 8-connected complement within the bounding box padded by one.
+    // Every component except the one surrounding the region is a hole.
+    let (i0, j0) = (min.0 as i64 - 1, min.1 as i64 - 1);
+    let (i1, j1) = (max.0 as i64 + 1, max.1 as i64 + 1);
+
+    let mut seen = HashSet::new();
+    let mut components = 0;
+    for i in i0..=i1 {
+        for j in j0..=j1 {
+            if inside((i, j)) || !seen.insert((i, j)) {
+                continue;
+            }
+            components += 1;
+            let mut stack = vec![(i, j)];
+            while let Some((i, j)) = stack.pop() {
+                for di in -1..=1 {
+                    for dj in -1..=1 {
+                        let next = (i + di, j + dj);
+                        if i0 <= next.0
+                            && next.0 <= i1
+                            && j0 <= next.1
+                            && next.1 <= j1
+                            && !inside(next)
+                            && seen.insert(next)
+                        {
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // The padding is always a single component outside the region.
+    return components - 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(input: &str, measure: &dyn Fn(&Region) -> usize) -> usize {
+        regions(&Grid2D::parse(input))
+            .iter()
+            .map(|region| region.area * measure(region))
+            .sum()
+    }
+
+    #[test]
+    fn garden_prices() {
+        // Examples from year 2024 day 12.
+        let small = "AAAA\nBBCD\nBBCC\nEEEC";
+        let nested = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO";
+        let e_shape = "EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE";
+        let touching = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA";
+
+        assert_eq!(price(small, &|r| r.perimeter), 140);
+        assert_eq!(price(nested, &|r| r.perimeter), 772);
+        assert_eq!(price(small, &|r| r.sides), 80);
+        assert_eq!(price(nested, &|r| r.sides), 436);
+        assert_eq!(price(e_shape, &|r| r.sides), 236);
+        assert_eq!(price(touching, &|r| r.sides), 368);
+    }
+
+    #[test]
+    fn region_shapes() {
+        let grid = Grid2D::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO");
+        let regions = regions(&grid);
+        assert_eq!(regions.len(), 5);
+
+        let outer = &regions[0];
+        assert_eq!(outer.area, 21);
+        assert_eq!(outer.bounding_box, ((0, 0), (4, 4)));
+        assert_eq!(outer.holes, 4);
+
+        let inner = &regions[1];
+        assert_eq!(inner.cells, vec![(1, 1)]);
+        assert_eq!((inner.perimeter, inner.sides, inner.holes), (4, 4, 0));
+
+        // Diagonally touching cells form a single hole.
+        let grid = Grid2D::parse("AAAA\nA.AA\nAA.A\nAAAA");
+        assert_eq!(regions_by(&grid, &|a, b| a == b)[0].holes, 1);
+    }
+
+    #[test]
+    fn labelling() {
+        let grid = Grid2D::parse_with("1299\n1329\n6669", &|c| c.to_digit(10).unwrap()).unwrap();
+        // Connect cells differing by at most one.
+        let (labels, count) = label_regions(&grid, &|a, b| a.abs_diff(*b) <= 1);
+        assert_eq!(count, 3);
+        assert_eq!(labels.to_string(), "0011\n0001\n2221");
+    }
+}
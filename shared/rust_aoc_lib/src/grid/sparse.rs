@@ -0,0 +1,309 @@
+use super::Grid2D;
+use crate::point::Point2;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::ops::Index;
+
+type Point = Point2<i64>;
+
+// An unbounded grid storing only the cells that were set, keyed by points with x the
+// column and y the row. Missing cells read as the default value. The bounding box of
+// the stored cells grows with inserts, and is recomputed when next needed after an
+// edge cell was removed.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    pub default: T,
+    // Inclusive (min, max) corners, None while the grid is empty.
+    bounds: Cell<Option<(Point, Point)>>,
+    // Whether bounds may be larger than the stored cells.
+    stale: Cell<bool>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: Cell::new(None),
+            stale: Cell::new(false),
+        }
+    }
+
+    // Parse a text map with one row per line, the top left character at (0, 0).
+    // Characters converted to None are left unset.
+    pub fn parse_with(input: &str, default: T, convert: &dyn Fn(char) -> Option<T>) -> Self {
+        let mut grid = Self::new(default);
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = convert(c) {
+                    grid.insert(Point2::new(x as i64, y as i64), value);
+                }
+            }
+        }
+        return grid;
+    }
+
+    // Store every cell of a dense grid that differs from the default, with (i, j) at x = j, y = i.
+    pub fn from_grid(grid: &Grid2D<T>, default: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        let mut sparse = Self::new(default);
+        for ((i, j), value) in grid.iter() {
+            if value != &sparse.default {
+                sparse.insert(Point2::new(j as i64, i as i64), value.clone());
+            }
+        }
+        return sparse;
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Remove all cells, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds.set(None);
+        self.stale.set(false);
+    }
+
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        if self.stale.replace(false) {
+            self.bounds.set(None);
+            for &pos in self.cells.keys() {
+                self.extend_bounds(pos);
+            }
+        }
+        self.bounds.get()
+    }
+
+    // Width and height of the bounding box.
+    pub fn size(&self) -> (usize, usize) {
+        self.bounds().map_or((0, 0), |(min, max)| {
+            ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize)
+        })
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    // The stored value, or the default for cells that were never set.
+    pub fn get(&self, pos: Point) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Point, value: T) -> Option<T> {
+        self.extend_bounds(pos);
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Point) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        // The box can only shrink if the removed cell was on its edge.
+        if let Some((min, max)) = self.bounds.get() {
+            if pos.x == min.x || pos.x == max.x || pos.y == min.y || pos.y == max.y {
+                self.stale.set(true);
+            }
+        }
+        return Some(value);
+    }
+
+    // Growing a stale box keeps it a superset of the cells, to be recomputed later.
+    fn extend_bounds(&self, pos: Point) {
+        self.bounds.set(Some(match self.bounds.get() {
+            None => (pos, pos),
+            Some((min, max)) => (
+                Point2::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Point2::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+        }));
+    }
+
+    // Positions of the stored cells in no particular order.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // Stored cells in reading order: by row, then by column.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let mut positions: Vec<Point> = self.cells.keys().copied().collect();
+        positions.sort_by_key(|pos| (pos.y, pos.x));
+        positions.into_iter().map(|pos| (pos, &self.cells[&pos]))
+    }
+
+    // Render the inclusive box from min to max as text, one line per row.
+    pub fn render_crop(&self, min: Point, max: Point, show: &dyn Fn(&T) -> char) -> String {
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| show(self.get(Point2::new(x, y))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Render the bounding box as text.
+    pub fn render(&self, show: &dyn Fn(&T) -> char) -> String {
+        match self.bounds() {
+            Some((min, max)) => self.render_crop(min, max, show),
+            None => String::new(),
+        }
+    }
+
+    // Copy the inclusive box from min to max into a dense grid, where (min.y, min.x) becomes (0, 0).
+    pub fn crop(&self, min: Point, max: Point) -> Grid2D<T>
+    where
+        T: Clone,
+    {
+        Grid2D::from_rows(
+            (min.y..=max.y)
+                .map(|y| {
+                    (min.x..=max.x)
+                        .map(|x| self.get(Point2::new(x, y)).clone())
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    // The bounding box as a dense grid, together with the point that became (0, 0).
+    pub fn to_grid(&self) -> (Grid2D<T>, Point)
+    where
+        T: Clone,
+    {
+        match self.bounds() {
+            Some((min, max)) => (self.crop(min, max), min),
+            None => (Grid2D::from_rows(Vec::new()), Point2::ORIGIN),
+        }
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        self.get(pos)
+    }
+}
+
+// Equal when the same cells are stored with the same default.
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default && self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+// Hashing in reading order, such that equal grids hash equally regardless of insertion order.
+impl<T: Hash> Hash for SparseGrid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.default.hash(state);
+        for (pos, value) in self.iter() {
+            pos.hash(state);
+            value.hash(state);
+        }
+    }
+}
+
+impl Display for SparseGrid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(&|&c| c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_tracking() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.insert(Point2::new(2, -1), '#');
+        grid.insert(Point2::new(-3, 4), '#');
+        grid.insert(Point2::new(0, 0), 'O');
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2::new(-3, -1), Point2::new(2, 4)))
+        );
+        assert_eq!(grid.size(), (6, 6));
+
+        // Removing an inner cell keeps the box, removing an edge cell shrinks it.
+        grid.remove(Point2::new(0, 0));
+        assert_eq!(grid.size(), (6, 6));
+        grid.remove(Point2::new(-3, 4));
+        assert_eq!(
+            grid.bounds(),
+            Some((Point2::new(2, -1), Point2::new(2, -1)))
+        );
+        assert_eq!(grid.remove(Point2::new(7, 7)), None);
+
+        assert_eq!(grid[Point2::new(2, -1)], '#');
+        assert_eq!(grid[Point2::new(100, 100)], '.');
+
+        // Bounds are recomputed lazily, after any number of removals.
+        grid.insert(Point2::new(5, 5), '#');
+        grid.remove(Point2::new(2, -1));
+        grid.insert(Point2::new(4, 6), '#');
+        assert_eq!(grid.bounds(), Some((Point2::new(4, 5), Point2::new(5, 6))));
+        grid.remove(Point2::new(4, 6));
+        grid.remove(Point2::new(5, 5));
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid, SparseGrid::new('.'));
+    }
+
+    #[test]
+    fn rendering() {
+        // Example from year 2024 day 15, storing only walls and boxes.
+        let input = "########\n#..O.O.#\n##@.O..#\n########";
+        let mut grid = SparseGrid::parse_with(input, '.', &|c| (c != '.').then_some(c));
+        assert_eq!(grid.len(), 25);
+        assert_eq!(grid.to_string(), input);
+
+        // Push the robot and boxes one step right, growing the world past the wall.
+        grid.remove(Point2::new(2, 2));
+        grid.insert(Point2::new(3, 2), '@');
+        grid.insert(Point2::new(8, 2), '#');
+        assert_eq!(grid.size(), (9, 4));
+        assert_eq!(
+            grid.render_crop(Point2::new(1, 1), Point2::new(4, 2), &|&c| c),
+            "..O.\n#.@O"
+        );
+
+        let (dense, origin) = grid.to_grid();
+        assert_eq!(origin, Point2::ORIGIN);
+        assert_eq!(dense[(2, 3)], '@');
+        assert_eq!(dense[(3, 8)], '.');
+    }
+
+    #[test]
+    fn reading_order() {
+        let grid = SparseGrid::from_grid(&Grid2D::parse("ab\n.d"), '.');
+        let order: String = grid.iter().map(|(_, c)| c).collect();
+        assert_eq!(order, "abd");
+
+        let mut grid = SparseGrid::new(0);
+        for (x, y) in [(5, 1), (-2, 3), (0, -4), (9, 1)] {
+            grid.insert(Point2::new(x, y), x);
+        }
+        let xs: Vec<i64> = grid.iter().map(|(_, &x)| x).collect();
+        assert_eq!(xs, vec![0, 5, 9, -2]);
+    }
+}
@@ -0,0 +1,158 @@
+use super::Grid2D;
+use std::collections::{HashMap, VecDeque};
+
+// A grid repeated infinitely in every direction. Tile (0, 0) is the grid itself.
+#[derive(Debug, Clone, Copy)]
+pub struct TiledView<'a, T> {
+    grid: &'a Grid2D<T>,
+}
+
+impl<T> Grid2D<T> {
+    // Panics for an empty grid, which has no cells to repeat.
+    pub fn tiled(&self) -> TiledView<'_, T> {
+        assert!(
+            self.rows() > 0 && self.cols() > 0,
+            "Cannot tile an empty grid."
+        );
+        TiledView { grid: self }
+    }
+}
+
+impl<'a, T> TiledView<'a, T> {
+    // Map a signed position to (position in the base grid, tile index).
+    pub fn wrap(&self, (i, j): (i64, i64)) -> ((usize, usize), (i64, i64)) {
+        let (n, m) = (self.grid.rows() as i64, self.grid.cols() as i64);
+        (
+            (i.rem_euclid(n) as usize, j.rem_euclid(m) as usize),
+            (i.div_euclid(n), j.div_euclid(m)),
+        )
+    }
+
+    pub fn tile(&self, pos: (i64, i64)) -> (i64, i64) {
+        self.wrap(pos).1
+    }
+
+    pub fn get(&self, pos: (i64, i64)) -> &'a T {
+        &self.grid[self.wrap(pos).0]
+    }
+
+    // Breadth-first search over passable cells of the infinite grid, up to max_steps from start.
+    pub fn bfs(
+        &self,
+        start: (i64, i64),
+        max_steps: usize,
+        passable: &dyn Fn(&T) -> bool,
+    ) -> TiledReach {
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(pos) = queue.pop_front() {
+            let d = distances[&pos];
+            if d == max_steps {
+                continue;
+            }
+            for (di, dj) in [(-1, 0), (0, 1), (1, 0), (0, -1)] {
+                let next = (pos.0 + di, pos.1 + dj);
+                if !distances.contains_key(&next) && passable(self.get(next)) {
+                    distances.insert(next, d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let tiles = distances.keys().map(|&pos| (pos, self.tile(pos))).collect();
+        return TiledReach { distances, tiles };
+    }
+}
+
+// Result of TiledView::bfs.
+#[derive(Debug, Clone)]
+pub struct TiledReach {
+    // Shortest number of steps to every visited position.
+    pub distances: HashMap<(i64, i64), usize>,
+    // Tile index of every visited position.
+    pub tiles: HashMap<(i64, i64), (i64, i64)>,
+}
+
+impl TiledReach {
+    // Number of positions where a walk of exactly steps can end. Since any walk
+    // can go back and forth, these are the positions within steps of equal parity.
+    pub fn count(&self, steps: usize) -> usize {
+        self.distances
+            .values()
+            .filter(|&&d| d <= steps && d % 2 == steps % 2)
+            .count()
+    }
+
+    // The same count, split by the tile each position lies in.
+    pub fn count_per_tile(&self, steps: usize) -> HashMap<(i64, i64), usize> {
+        let mut counts = HashMap::new();
+        for (pos, &d) in &self.distances {
+            if d <= steps && d % 2 == steps % 2 {
+                *counts.entry(self.tiles[pos]).or_insert(0) += 1;
+            }
+        }
+        return counts;
+    }
+
+    // Number of visited positions at (even, odd) distance from start.
+    pub fn parity_counts(&self) -> (usize, usize) {
+        let odd = self.distances.values().filter(|&&d| d % 2 == 1).count();
+        (self.distances.len() - odd, odd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example from year 2023 day 21.
+    const GARDEN: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn wrapping() {
+        let grid = Grid2D::parse(GARDEN);
+        let view = grid.tiled();
+
+        assert_eq!(view.wrap((5, 5)), ((5, 5), (0, 0)));
+        assert_eq!(view.wrap((-1, 11)), ((10, 0), (-1, 1)));
+        assert_eq!(view.wrap((-12, -23)), ((10, 10), (-2, -3)));
+        assert_eq!(view.get((5, 5 + 11 * 7)), &'S');
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot tile an empty grid.")]
+    fn empty() {
+        Grid2D::parse("").tiled();
+    }
+
+    #[test]
+    fn infinite_garden() {
+        let grid = Grid2D::parse(GARDEN);
+        let (i, j) = grid.find(&'S').unwrap();
+        let start = (i as i64, j as i64);
+        let reach = grid.tiled().bfs(start, 100, &|&c| c != '#');
+
+        assert_eq!(reach.count(6), 16);
+        assert_eq!(reach.count(10), 50);
+        assert_eq!(reach.count(50), 1594);
+        assert_eq!(reach.count(100), 6536);
+
+        let per_tile = reach.count_per_tile(10);
+        assert_eq!(per_tile.values().sum::<usize>(), 50);
+        assert!(per_tile.contains_key(&(0, -1)));
+
+        let (even, odd) = reach.parity_counts();
+        assert_eq!(even + odd, reach.distances.len());
+    }
+}
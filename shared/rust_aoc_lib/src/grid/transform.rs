@@ -0,0 +1,197 @@
+// Transformations of rectangular grids stored as rows: grid[i][j].
+// Copying versions take a reference, in-place versions end with _mut.
+
+use std::iter::zip;
+
+pub fn transpose<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let cols = grid.first().map_or(0, |row| row.len());
+    (0..cols)
+        .map(|j| grid.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+pub fn transpose_mut<T: Clone>(grid: &mut Vec<Vec<T>>) {
+    *grid = transpose(grid);
+}
+
+// Rotate clockwise by the given number of quarter turns. Negative turns rotate counterclockwise.
+pub fn rotate<T: Clone>(grid: &[Vec<T>], quarter_turns: i32) -> Vec<Vec<T>> {
+    let mut result = grid.to_vec();
+    rotate_mut(&mut result, quarter_turns);
+    return result;
+}
+
+pub fn rotate_mut<T: Clone>(grid: &mut Vec<Vec<T>>, quarter_turns: i32) {
+    match quarter_turns.rem_euclid(4) {
+        0 => {}
+        1 => {
+            // Clockwise is transposition followed by mirroring left-right.
+            transpose_mut(grid);
+            flip_horizontal_mut(grid);
+        }
+        2 => {
+            flip_vertical_mut(grid);
+            flip_horizontal_mut(grid);
+        }
+        _ => {
+            transpose_mut(grid);
+            flip_vertical_mut(grid);
+        }
+    }
+}
+
+// Mirror left-right.
+pub fn flip_horizontal<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let mut result = grid.to_vec();
+    flip_horizontal_mut(&mut result);
+    return result;
+}
+
+pub fn flip_horizontal_mut<T>(grid: &mut [Vec<T>]) {
+    grid.iter_mut().for_each(|row| row.reverse());
+}
+
+// Mirror top-bottom.
+pub fn flip_vertical<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let mut result = grid.to_vec();
+    flip_vertical_mut(&mut result);
+    return result;
+}
+
+pub fn flip_vertical_mut<T>(grid: &mut [Vec<T>]) {
+    grid.reverse();
+}
+
+// Move every movable cell in the direction step = (di, dj) until it hits
+// something that is not empty, e.g. rolling rocks 'O' over '.' when tilting.
+// Only the four orthogonal unit steps are supported.
+pub fn roll<T: PartialEq>(grid: &mut [Vec<T>], step: (i64, i64), movable: &T, empty: &T) {
+    let (n, m) = (grid.len(), grid.first().map_or(0, |row| row.len()));
+
+    // Lines of positions ordered from the side cells roll towards.
+    let lines: Vec<Vec<(usize, usize)>> = match step {
+        (-1, 0) => (0..m).map(|j| (0..n).map(|i| (i, j)).collect()).collect(),
+        (1, 0) => (0..m)
+            .map(|j| (0..n).rev().map(|i| (i, j)).collect())
+            .collect(),
+        (0, -1) => (0..n).map(|i| (0..m).map(|j| (i, j)).collect()).collect(),
+        (0, 1) => (0..n)
+            .map(|i| (0..m).rev().map(|j| (i, j)).collect())
+            .collect(),
+        _ => panic!("Can only roll in orthogonal unit steps, not {step:?}."),
+    };
+
+    for line in lines {
+        // Index of the first free position in line.
+        let mut free = 0;
+        for (k, &(i, j)) in line.iter().enumerate() {
+            if &grid[i][j] == movable {
+                // All positions from free up to k hold empty cells.
+                swap_cells(grid, line[free], (i, j));
+                free += 1;
+            } else if &grid[i][j] != empty {
+                free = k + 1;
+            }
+        }
+    }
+}
+
+fn swap_cells<T>(grid: &mut [Vec<T>], a: (usize, usize), b: (usize, usize)) {
+    if a.0 == b.0 {
+        grid[a.0].swap(a.1, b.1);
+    } else {
+        let (low, high) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        let (top, bottom) = grid.split_at_mut(high.0);
+        std::mem::swap(&mut top[low.0][low.1], &mut bottom[0][high.1]);
+    }
+}
+
+// Horizontal mirror axes with at most max_mismatches differing cells.
+// Returns pairs (rows above the axis, number of mismatches).
+pub fn mirror_rows<T: PartialEq>(grid: &[Vec<T>], max_mismatches: usize) -> Vec<(usize, usize)> {
+    let mut axes = Vec::new();
+    for i in 1..grid.len() {
+        let distance_to_edge = usize::min(i, grid.len() - i);
+        let mut mismatches = 0;
+        for d in 1..=distance_to_edge {
+            mismatches += zip(&grid[i - d], &grid[i + d - 1])
+                .filter(|(a, b)| a != b)
+                .count();
+            if mismatches > max_mismatches {
+                break;
+            }
+        }
+        if mismatches <= max_mismatches {
+            axes.push((i, mismatches));
+        }
+    }
+    return axes;
+}
+
+// Vertical mirror axes. Returns pairs (columns left of the axis, number of mismatches).
+pub fn mirror_cols<T: PartialEq + Clone>(
+    grid: &[Vec<T>],
+    max_mismatches: usize,
+) -> Vec<(usize, usize)> {
+    mirror_rows(&transpose(grid), max_mismatches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<Vec<char>> {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    #[test]
+    fn rotations() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        assert_eq!(transpose(&grid), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(rotate(&grid, 1), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+        assert_eq!(rotate(&grid, 2), vec![vec![6, 5, 4], vec![3, 2, 1]]);
+        assert_eq!(rotate(&grid, 3), vec![vec![3, 6], vec![2, 5], vec![1, 4]]);
+        assert_eq!(rotate(&grid, -1), rotate(&grid, 3));
+        assert_eq!(flip_horizontal(&grid), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+        assert_eq!(flip_vertical(&grid), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotate_mut(&mut rotated, 1);
+        }
+        assert_eq!(rotated, grid);
+    }
+
+    #[test]
+    fn rolling() {
+        let mut grid = parse("O.#\n.O.\nO.O");
+
+        roll(&mut grid, (-1, 0), &'O', &'.');
+        assert_eq!(grid, parse("OO#\nO.O\n..."));
+
+        roll(&mut grid, (0, 1), &'O', &'.');
+        assert_eq!(grid, parse("OO#\n.OO\n..."));
+
+        roll(&mut grid, (1, 0), &'O', &'.');
+        assert_eq!(grid, parse("..#\n.O.\nOOO"));
+
+        roll(&mut grid, (0, -1), &'O', &'.');
+        assert_eq!(grid, parse("..#\nO..\nOOO"));
+    }
+
+    #[test]
+    fn mirrors() {
+        // Example from year 2023 day 13.
+        let pattern =
+            parse("#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.");
+        assert_eq!(mirror_cols(&pattern, 0), vec![(5, 0)]);
+        assert_eq!(mirror_rows(&pattern, 0), vec![]);
+        // The smudge fixes a single mismatch.
+        let smudged: Vec<_> = mirror_rows(&pattern, 1)
+            .into_iter()
+            .filter(|&(_, mismatches)| mismatches == 1)
+            .collect();
+        assert_eq!(smudged, vec![(3, 1)]);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::graph::DiGraph;
+use crate::point::Point3;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+type Point = Point3<i64>;
+
+// Offsets to the six face-adjacent cells.
+pub const FACES: [Point; 6] = [
+    Point3 { x: 1, y: 0, z: 0 },
+    Point3 { x: -1, y: 0, z: 0 },
+    Point3 { x: 0, y: 1, z: 0 },
+    Point3 { x: 0, y: -1, z: 0 },
+    Point3 { x: 0, y: 0, z: 1 },
+    Point3 { x: 0, y: 0, z: -1 },
+];
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub fn coord(self, p: Point) -> i64 {
+        match self {
+            Axis::X => p.x,
+            Axis::Y => p.y,
+            Axis::Z => p.z,
+        }
+    }
+
+    fn with_coord(self, p: Point, value: i64) -> Point {
+        match self {
+            Axis::X => Point3 { x: value, ..p },
+            Axis::Y => Point3 { y: value, ..p },
+            Axis::Z => Point3 { z: value, ..p },
+        }
+    }
+}
+
+// Axis-aligned box of integer cells, where both corners are included.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Cuboid {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Cuboid {
+    // The box spanned by any two opposite corners.
+    pub fn new(a: Point, b: Point) -> Self {
+        Self {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    // Smallest box containing all points, None if there are none.
+    pub fn bounding(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                None => Cuboid::new(p, p),
+                Some(Cuboid { min, max }) => Cuboid {
+                    min: Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                    max: Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+                },
+            })
+        })
+    }
+
+    // Number of cells along each axis.
+    pub fn size(&self) -> Point {
+        self.max - self.min + Point3::new(1, 1, 1)
+    }
+
+    pub fn volume(&self) -> i64 {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    pub fn surface_area(&self) -> i64 {
+        let size = self.size();
+        2 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.min.x <= p.x
+            && p.x <= self.max.x
+            && self.min.y <= p.y
+            && p.y <= self.max.y
+            && self.min.z <= p.z
+            && p.z <= self.max.z
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = Point3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Point3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+    }
+
+    pub fn intersects(&self, other: &Cuboid) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn translate(&self, offset: Point) -> Cuboid {
+        Cuboid {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    // All cells of the box, x varying fastest.
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        (self.min.z..=self.max.z).flat_map(move |z| {
+            (self.min.y..=self.max.y)
+                .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+
+    // The box flattened onto the plane perpendicular to axis.
+    fn shadow(&self, axis: Axis) -> Cuboid {
+        Cuboid {
+            min: axis.with_coord(self.min, 0),
+            max: axis.with_coord(self.max, 0),
+        }
+    }
+}
+
+// Parses two corners "x,y,z~x,y,z".
+impl FromStr for Cuboid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_point = |s: &str| -> Result<Point, String> {
+            let coords: Vec<i64> = s
+                .split(',')
+                .map(|c| {
+                    c.trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid coordinate {c:?}."))
+                })
+                .collect::<Result<_, _>>()?;
+            match coords[..] {
+                [x, y, z] => Ok(Point3::new(x, y, z)),
+                _ => Err(format!("Expected three coordinates, not {s:?}.")),
+            }
+        };
+        let (a, b) = s
+            .split_once('~')
+            .ok_or(format!("Expected corners separated by '~', not {s:?}."))?;
+        Ok(Cuboid::new(parse_point(a)?, parse_point(b)?))
+    }
+}
+
+// Result of settling boxes.
+#[derive(Debug, Clone)]
+pub struct Settled {
+    // Boxes at rest, in the order they were given.
+    pub boxes: Vec<Cuboid>,
+    // Arc i -> j if box j rests directly on box i. Contains every box as a node.
+    pub supports: DiGraph<usize, ()>,
+}
+
+// Drop boxes towards decreasing coordinates along axis until they rest on the floor,
+// the lowest allowed coordinate, or on another box.
+pub fn settle(boxes: &[Cuboid], axis: Axis, floor: i64) -> Settled {
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by_key(|&k| axis.coord(boxes[k].min));
+
+    let mut settled: Vec<Option<Cuboid>> = vec![None; boxes.len()];
+    let mut supports = DiGraph::new();
+    for k in order {
+        let shadow = boxes[k].shadow(axis);
+        // Boxes at rest in the way of k.
+        let below: Vec<usize> = (0..boxes.len())
+            .filter(|&i| settled[i].is_some_and(|other| other.shadow(axis).intersects(&shadow)))
+            .collect();
+        let top = |i: usize| axis.coord(settled[i].unwrap().max);
+        let rest = below.iter().map(|&i| top(i) + 1).fold(floor, i64::max);
+
+        supports.add_node(k);
+        for &i in &below {
+            if top(i) + 1 == rest {
+                supports.add_directed_edge(i, k, ());
+            }
+        }
+        let drop = axis.coord(boxes[k].min) - rest;
+        settled[k] = Some(boxes[k].translate(axis.with_coord(Point3::ORIGIN, -drop)));
+    }
+
+    return Settled {
+        boxes: settled.into_iter().map(Option::unwrap).collect(),
+        supports,
+    };
+}
+
+// Number of cell faces not shared with another cell.
+pub fn surface_area(cells: &HashSet<Point>) -> usize {
+    cells
+        .iter()
+        .flat_map(|&p| FACES.iter().map(move |&d| p + d))
+        .filter(|n| !cells.contains(n))
+        .count()
+}
+
+// Number of cell faces reachable from outside, excluding the faces of enclosed cavities.
+pub fn exterior_surface_area(cells: &HashSet<Point>) -> usize {
+    let Some(bounds) = Cuboid::bounding(cells.iter().copied()) else {
+        return 0;
+    };
+    // Flood fill the air within the bounding box grown by one, counting the faces it touches.
+    let bounds = Cuboid::new(
+        bounds.min - Point3::new(1, 1, 1),
+        bounds.max + Point3::new(1, 1, 1),
+    );
+
+    let mut faces = 0;
+    let mut seen = HashSet::from([bounds.min]);
+    let mut stack = vec![bounds.min];
+    while let Some(p) = stack.pop() {
+        for &d in &FACES {
+            let next = p + d;
+            if cells.contains(&next) {
+                faces += 1;
+            } else if bounds.contains(next) && seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    return faces;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boxes() {
+        let a: Cuboid = "0,0,0~2,3,4".parse().unwrap();
+        let b = Cuboid::new(Point3::new(3, 3, 3), Point3::new(1, 1, 1));
+        assert_eq!(a.volume(), 60);
+        assert_eq!(a.surface_area(), 2 * (12 + 20 + 15));
+        assert_eq!(a.cells().count(), 60);
+        assert_eq!(a.intersection(&b), Some("1,1,1~2,3,3".parse().unwrap()));
+        assert_eq!(a.intersection(&b).unwrap().volume(), 18);
+        assert!(!a.intersects(&b.translate(Point3::new(0, 0, 4))));
+        assert!(a.contains(Point3::new(2, 3, 4)));
+        assert!("1,2~3,4,5".parse::<Cuboid>().is_err());
+    }
+
+    #[test]
+    fn falling_bricks() {
+        // Example from year 2023 day 22.
+        let bricks: Vec<Cuboid> = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9"
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let Settled { boxes, supports } = settle(&bricks, Axis::Z, 1);
+
+        assert_eq!(boxes[0], bricks[0]);
+        assert_eq!(boxes[6], "1,1,5~1,1,6".parse().unwrap());
+        assert_eq!(supports.out_degree(&0), 2);
+        assert_eq!(supports.in_degree(&6), 1);
+
+        // Bricks that can be removed without any other falling.
+        let safe = (0..boxes.len())
+            .filter(|i| {
+                let above: Vec<usize> = supports.successors(i);
+                above.iter().all(|j| supports.in_degree(j) > 1)
+            })
+            .count();
+        assert_eq!(safe, 5);
+
+        // Sum over all bricks of the number of other bricks that fall when it is removed.
+        let mut falling = 0;
+        for i in 0..boxes.len() {
+            let mut fallen = HashSet::from([i]);
+            let mut order: Vec<usize> = (0..boxes.len()).collect();
+            order.sort_by_key(|&j| boxes[j].min.z);
+            for j in order {
+                let below: Vec<usize> = supports.predecessors(&j);
+                if !below.is_empty() && below.iter().all(|k| fallen.contains(k)) {
+                    fallen.insert(j);
+                }
+            }
+            falling += fallen.len() - 1;
+        }
+        assert_eq!(falling, 7);
+    }
+
+    #[test]
+    fn surfaces() {
+        // Example from year 2022 day 18.
+        let cells: HashSet<Point> =
+            "2,2,2 1,2,2 3,2,2 2,1,2 2,3,2 2,2,1 2,2,3 2,2,4 2,2,6 1,2,5 3,2,5 2,1,5 2,3,5"
+                .split(' ')
+                .map(|s| {
+                    let c: Vec<i64> = s.split(',').map(|c| c.parse().unwrap()).collect();
+                    Point3::new(c[0], c[1], c[2])
+                })
+                .collect();
+        assert_eq!(surface_area(&cells), 64);
+        assert_eq!(exterior_surface_area(&cells), 58);
+
+        let cube: HashSet<Point> = "0,0,0~2,2,2".parse::<Cuboid>().unwrap().cells().collect();
+        assert_eq!(
+            surface_area(&cube) as i64,
+            Cuboid::new(Point3::ORIGIN, Point3::new(2, 2, 2)).surface_area()
+        );
+        assert_eq!(exterior_surface_area(&HashSet::new()), 0);
+    }
+}
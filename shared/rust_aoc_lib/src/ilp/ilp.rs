@@ -0,0 +1,428 @@
+// Linear and integer linear programs with integer coefficients, solved exactly:
+// the simplex method over rationals for the relaxation, and branch-and-bound on
+// fractional variables for integer solutions. Meant for puzzle-sized problems with
+// a few dozen variables, not as a general purpose solver.
+
+use super::simplex::{self, LpResult};
+use crate::rational::Rational;
+
+type Q = Rational<i128>;
+// Pairs (column, coefficient) of the simplex columns.
+type SparseRow = Vec<(usize, i128)>;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Relation {
+    Le,
+    Eq,
+    Ge,
+}
+
+// coefficients . x relation rhs
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Constraint {
+    pub coefficients: Vec<i64>,
+    pub relation: Relation,
+    pub rhs: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome<T> {
+    Optimal { x: Vec<T>, value: T },
+    Infeasible,
+    Unbounded,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Problem {
+    pub objective: Vec<i64>,
+    pub constraints: Vec<Constraint>,
+    // Bounds per variable, None if unbounded in that direction. By default all variables
+    // are non-negative.
+    pub lower: Vec<Option<i64>>,
+    pub upper: Vec<Option<i64>>,
+    // Relaxations solve() may try before giving up, as branching never ends when the
+    // feasible region is unbounded but holds no integer points.
+    pub node_limit: usize,
+    maximise: bool,
+}
+
+impl Problem {
+    // A problem with one variable per objective coefficient and no constraints yet.
+    pub fn minimise(objective: Vec<i64>) -> Self {
+        let n = objective.len();
+        Self {
+            objective,
+            constraints: vec![],
+            lower: vec![Some(0); n],
+            upper: vec![None; n],
+            node_limit: 100_000,
+            maximise: false,
+        }
+    }
+
+    pub fn maximise(objective: Vec<i64>) -> Self {
+        Self {
+            maximise: true,
+            ..Self::minimise(objective)
+        }
+    }
+
+    pub fn variables(&self) -> usize {
+        self.objective.len()
+    }
+
+    // Panics unless there is one coefficient per variable.
+    pub fn constrain(&mut self, coefficients: Vec<i64>, relation: Relation, rhs: i64) {
+        assert_eq!(
+            coefficients.len(),
+            self.variables(),
+            "Constraint must have one coefficient per variable."
+        );
+        self.constraints.push(Constraint {
+            coefficients,
+            relation,
+            rhs,
+        });
+    }
+
+    pub fn bound(&mut self, var: usize, lower: Option<i64>, upper: Option<i64>) {
+        self.lower[var] = lower;
+        self.upper[var] = upper;
+    }
+
+    // Optimum over the reals. An error if the exact arithmetic overflows.
+    pub fn solve_relaxation(&self) -> Result<Outcome<Q>, String> {
+        self.relaxation(&self.lower, &self.upper)
+    }
+
+    // Optimum over the integers. Unbounded if the relaxation is. An error if a
+    // solution or branching bound does not fit in i64, if the exact arithmetic of a
+    // relaxation overflows, or after node_limit relaxations.
+    pub fn solve(&self) -> Result<Outcome<i64>, String> {
+        let to_i64 = |n: i128| i64::try_from(n).map_err(|_| format!("{n} does not fit in i64."));
+        // Best integer solution so far, with its objective to minimise.
+        let mut best: Option<(Vec<i64>, i128)> = None;
+        let mut stack = vec![(self.lower.clone(), self.upper.clone())];
+        let mut nodes = 0;
+        while let Some((lower, upper)) = stack.pop() {
+            if nodes == self.node_limit {
+                return Err(format!("No optimum found within {nodes} nodes."));
+            }
+            nodes += 1;
+            let (x, value) = match self.relaxation(&lower, &upper)? {
+                Outcome::Optimal { x, value } => (x, value),
+                Outcome::Infeasible => continue,
+                Outcome::Unbounded => return Ok(Outcome::Unbounded),
+            };
+            // With integer coefficients, integer solutions have integer objectives.
+            let bound = if self.maximise {
+                -value.floor()
+            } else {
+                value.ceil()
+            };
+            if best.as_ref().is_some_and(|&(_, b)| bound >= b) {
+                continue;
+            }
+            match x.iter().position(|v| !v.is_integer()) {
+                None => {
+                    let x = x.iter().map(|v| to_i64(v.numer()));
+                    best = Some((x.collect::<Result<_, _>>()?, bound));
+                }
+                Some(j) => {
+                    let mut below = upper.clone();
+                    below[j] = Some(to_i64(x[j].floor())?);
+                    let mut above = lower.clone();
+                    above[j] = Some(to_i64(x[j].ceil())?);
+                    stack.push((lower, below));
+                    stack.push((above, upper));
+                }
+            }
+        }
+        return match best {
+            Some((x, value)) => Ok(Outcome::Optimal {
+                x,
+                value: to_i64(if self.maximise { -value } else { value })?,
+            }),
+            None => Ok(Outcome::Infeasible),
+        };
+    }
+
+    fn relaxation(
+        &self,
+        lower: &[Option<i64>],
+        upper: &[Option<i64>],
+    ) -> Result<Outcome<Q>, String> {
+        let overflow = || "Overflow in the relaxation.".to_string();
+        // Substitute x = offset + sum of sign * y over the non-negative simplex columns y.
+        let mut columns: Vec<SparseRow> = vec![];
+        let mut offsets = vec![];
+        let mut next = 0;
+        let mut rows: Vec<(SparseRow, Relation, i128)> = vec![];
+        for j in 0..self.variables() {
+            let (offset, column) = match (lower[j], upper[j]) {
+                (Some(l), Some(u)) if u < l => return Ok(Outcome::Infeasible),
+                (Some(l), Some(u)) => {
+                    rows.push((vec![(next, 1)], Relation::Le, u as i128 - l as i128));
+                    (l, vec![(next, 1)])
+                }
+                (Some(l), None) => (l, vec![(next, 1)]),
+                (None, Some(u)) => (u, vec![(next, -1)]),
+                (None, None) => (0, vec![(next, 1), (next + 1, -1)]),
+            };
+            next += column.len();
+            offsets.push(offset as i128);
+            columns.push(column);
+        }
+        for constraint in &self.constraints {
+            let mut row = vec![];
+            let mut rhs = constraint.rhs as i128;
+            for (j, &a) in constraint.coefficients.iter().enumerate() {
+                rhs = rhs
+                    .checked_sub(a as i128 * offsets[j])
+                    .ok_or_else(overflow)?;
+                row.extend(columns[j].iter().map(|&(c, sign)| (c, sign * a as i128)));
+            }
+            rows.push((row, constraint.relation, rhs));
+        }
+
+        // Inequalities get a slack column each.
+        let slacks = rows.iter().filter(|r| r.1 != Relation::Eq).count();
+        let (mut a, mut b) = (vec![], vec![]);
+        let mut slack = next;
+        for (row, relation, rhs) in rows {
+            let mut dense = vec![Q::ZERO; next + slacks];
+            for (c, x) in row {
+                dense[c] += x;
+            }
+            match relation {
+                Relation::Le => dense[slack] = Q::ONE,
+                Relation::Ge => dense[slack] = -Q::ONE,
+                Relation::Eq => {}
+            }
+            slack += (relation != Relation::Eq) as usize;
+            a.push(dense);
+            b.push(Q::from(rhs));
+        }
+        let sense = if self.maximise { -1 } else { 1 };
+        let mut cost = vec![Q::ZERO; next + slacks];
+        for (j, column) in columns.iter().enumerate() {
+            for &(c, sign) in column {
+                cost[c] = Q::from(sense * sign * self.objective[j] as i128);
+            }
+        }
+
+        let y = match simplex::minimise(&a, &b, &cost)? {
+            LpResult::Optimal(y) => y,
+            LpResult::Infeasible => return Ok(Outcome::Infeasible),
+            LpResult::Unbounded => return Ok(Outcome::Unbounded),
+        };
+        let x: Option<Vec<Q>> = columns
+            .iter()
+            .zip(&offsets)
+            .map(|(column, &offset)| {
+                column.iter().try_fold(Q::from(offset), |acc, &(c, sign)| {
+                    acc.checked_add(y[c].checked_mul(Q::from(sign))?)
+                })
+            })
+            .collect();
+        let x = x.ok_or_else(overflow)?;
+        let value = x
+            .iter()
+            .zip(&self.objective)
+            .try_fold(Q::ZERO, |acc, (&x, &c)| {
+                acc.checked_add(x.checked_mul(Q::from(c as i128))?)
+            })
+            .ok_or_else(overflow)?;
+        return Ok(Outcome::Optimal { x, value });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn textbook() {
+        // Maximise 5x + 4y subject to 6x + 4y <= 24 and x + 2y <= 6.
+        let mut problem = Problem::maximise(vec![5, 4]);
+        problem.constrain(vec![6, 4], Relation::Le, 24);
+        problem.constrain(vec![1, 2], Relation::Le, 6);
+        assert_eq!(
+            problem.solve_relaxation().unwrap(),
+            Outcome::Optimal {
+                x: vec![Q::from(3), Q::new(3, 2)],
+                value: Q::from(21)
+            }
+        );
+        assert_eq!(
+            problem.solve().unwrap(),
+            Outcome::Optimal {
+                x: vec![4, 0],
+                value: 20
+            }
+        );
+
+        // Cutting off (4, 0) with an upper bound.
+        problem.bound(0, Some(0), Some(3));
+        assert_eq!(
+            problem.solve().unwrap(),
+            Outcome::Optimal {
+                x: vec![3, 1],
+                value: 19
+            }
+        );
+    }
+
+    #[test]
+    fn factory_machines() {
+        // Example from year 2025 day 10 part 2: press buttons, each incrementing some
+        // counters, to reach the joltage targets in as few presses as possible.
+        let input = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+        let numbers = |s: &str| -> Vec<i64> {
+            s[1..s.len() - 1]
+                .split(',')
+                .map(|n| n.parse().unwrap())
+                .collect()
+        };
+        let presses: Vec<i64> = input
+            .lines()
+            .map(|line| {
+                let parts: Vec<&str> = line.split(' ').collect();
+                let buttons: Vec<Vec<i64>> = parts[1..parts.len() - 1]
+                    .iter()
+                    .map(|s| numbers(s))
+                    .collect();
+                let targets = numbers(parts[parts.len() - 1]);
+
+                let mut problem = Problem::minimise(vec![1; buttons.len()]);
+                for (k, &target) in targets.iter().enumerate() {
+                    let row = buttons
+                        .iter()
+                        .map(|b| b.contains(&(k as i64)) as i64)
+                        .collect();
+                    problem.constrain(row, Relation::Eq, target);
+                }
+                match problem.solve().unwrap() {
+                    Outcome::Optimal { value, .. } => value,
+                    outcome => panic!("Unexpected {outcome:?}."),
+                }
+            })
+            .collect();
+        assert_eq!(presses, vec![10, 12, 11]);
+    }
+
+    #[test]
+    fn bounds() {
+        // Minimise x subject to 2x >= -7 for a free variable x.
+        let mut problem = Problem::minimise(vec![1]);
+        problem.bound(0, None, None);
+        problem.constrain(vec![2], Relation::Ge, -7);
+        assert_eq!(
+            problem.solve_relaxation().unwrap(),
+            Outcome::Optimal {
+                x: vec![Q::new(-7, 2)],
+                value: Q::new(-7, 2)
+            }
+        );
+        assert_eq!(
+            problem.solve().unwrap(),
+            Outcome::Optimal {
+                x: vec![-3],
+                value: -3
+            }
+        );
+
+        // Maximise x + y with x <= 5 by bound, y between -10 and -2.
+        let mut problem = Problem::maximise(vec![1, 1]);
+        problem.bound(0, None, Some(5));
+        problem.bound(1, Some(-10), Some(-2));
+        problem.constrain(vec![1, -1], Relation::Le, 6);
+        assert_eq!(
+            problem.solve().unwrap(),
+            Outcome::Optimal {
+                x: vec![4, -2],
+                value: 2
+            }
+        );
+    }
+
+    #[test]
+    fn degenerate_problems() {
+        let mut problem = Problem::minimise(vec![-1, 0]);
+        problem.constrain(vec![0, 1], Relation::Le, 3);
+        assert_eq!(problem.solve().unwrap(), Outcome::Unbounded);
+
+        let mut problem = Problem::minimise(vec![1, 1]);
+        problem.constrain(vec![1, 1], Relation::Eq, 1);
+        problem.constrain(vec![2, 2], Relation::Eq, 3);
+        assert_eq!(problem.solve_relaxation().unwrap(), Outcome::Infeasible);
+
+        // Feasible over the reals only.
+        let mut problem = Problem::minimise(vec![1]);
+        problem.constrain(vec![2], Relation::Eq, 1);
+        assert!(matches!(
+            problem.solve_relaxation().unwrap(),
+            Outcome::Optimal { .. }
+        ));
+        assert_eq!(problem.solve().unwrap(), Outcome::Infeasible);
+
+        let mut problem = Problem::minimise(vec![0]);
+        problem.bound(0, Some(2), Some(1));
+        assert_eq!(problem.solve().unwrap(), Outcome::Infeasible);
+
+        // 2x - 2y = 1 has real solutions arbitrarily far out, but no integer ones.
+        let mut problem = Problem::minimise(vec![0, 0]);
+        problem.constrain(vec![2, -2], Relation::Eq, 1);
+        problem.node_limit = 1000;
+        assert!(problem.solve().is_err());
+
+        // Bounds whose width exceeds i64.
+        let mut problem = Problem::minimise(vec![1]);
+        problem.bound(
+            0,
+            Some(-5_000_000_000_000_000_000),
+            Some(5_000_000_000_000_000_000),
+        );
+        assert_eq!(
+            problem.solve().unwrap(),
+            Outcome::Optimal {
+                x: vec![-5_000_000_000_000_000_000],
+                value: -5_000_000_000_000_000_000
+            }
+        );
+
+        // Fractions in the tableau outgrow i128 for a dense system of large coefficients.
+        let primes: [i64; 16] = [
+            999999999999999989,
+            999999999999999877,
+            999999999999999863,
+            999999999999999829,
+            999999999999999791,
+            999999999999999769,
+            999999999999999739,
+            999999999999999721,
+            999999999999999713,
+            999999999999999677,
+            999999999999999659,
+            999999999999999623,
+            999999999999999613,
+            999999999999999593,
+            999999999999999563,
+            999999999999999539,
+        ];
+        let mut problem = Problem::maximise(vec![1; 4]);
+        for row in primes.chunks(4) {
+            problem.constrain(row.to_vec(), Relation::Le, 999_999_999_999_999_937);
+        }
+        assert!(problem.solve_relaxation().is_err());
+        assert!(problem.solve().is_err());
+
+        // The optimum is integer, but too large for i64.
+        let mut problem = Problem::maximise(vec![2]);
+        problem.bound(0, Some(0), Some(i64::MAX));
+        assert!(problem.solve().is_err());
+    }
+}
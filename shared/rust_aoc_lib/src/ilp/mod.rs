@@ -0,0 +1,4 @@
+mod ilp;
+mod simplex;
+
+pub use ilp::*;
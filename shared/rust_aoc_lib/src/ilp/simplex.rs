@@ -0,0 +1,136 @@
+// Two-phase simplex on a dense tableau of rationals. Bland's rule picks the entering
+// and leaving columns, which is slow but can never cycle.
+
+use crate::rational::Rational;
+
+type Q = Rational<i128>;
+
+fn overflow() -> String {
+    "Overflow in the simplex tableau.".to_string()
+}
+
+pub(super) enum LpResult {
+    Optimal(Vec<Q>),
+    Infeasible,
+    Unbounded,
+}
+
+struct Tableau {
+    // One row per constraint, with the right-hand side in the last column.
+    rows: Vec<Vec<Q>>,
+    // The basic variable of each row.
+    basis: Vec<usize>,
+    rhs: usize,
+}
+
+impl Tableau {
+    fn pivot(&mut self, r: usize, c: usize) -> Result<(), String> {
+        let factor = self.rows[r][c];
+        for x in self.rows[r].iter_mut() {
+            *x = x.checked_div(factor).ok_or_else(overflow)?;
+        }
+        let pivot_row = self.rows[r].clone();
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            let factor = row[c];
+            if i != r && factor != Q::ZERO {
+                for (x, &p) in row.iter_mut().zip(&pivot_row) {
+                    let product = factor.checked_mul(p).ok_or_else(overflow)?;
+                    *x = x.checked_sub(product).ok_or_else(overflow)?;
+                }
+            }
+        }
+        self.basis[r] = c;
+        return Ok(());
+    }
+
+    // Minimise cost over the first allowed columns, starting from the current basis.
+    // Returns false if the objective is unbounded.
+    fn optimise(&mut self, cost: &[Q], allowed: usize) -> Result<bool, String> {
+        loop {
+            let reduced = |j: usize| {
+                self.rows
+                    .iter()
+                    .zip(&self.basis)
+                    .try_fold(cost[j], |acc, (row, &b)| {
+                        acc.checked_sub(cost[b].checked_mul(row[j])?)
+                    })
+            };
+            let mut entering = None;
+            for j in (0..allowed).filter(|j| !self.basis.contains(j)) {
+                if reduced(j).ok_or_else(overflow)? < Q::ZERO {
+                    entering = Some(j);
+                    break;
+                }
+            }
+            let Some(entering) = entering else {
+                return Ok(true);
+            };
+            // Rows limiting the entering column, by ratio and then by basic variable.
+            let mut limits = vec![];
+            for (i, row) in self.rows.iter().enumerate() {
+                if row[entering] > Q::ZERO {
+                    let ratio = row[self.rhs]
+                        .checked_div(row[entering])
+                        .ok_or_else(overflow)?;
+                    limits.push((ratio, self.basis[i], i));
+                }
+            }
+            let Some(&(_, _, leaving)) = limits.iter().min() else {
+                return Ok(false);
+            };
+            self.pivot(leaving, entering)?;
+        }
+    }
+}
+
+// Minimise cost . x subject to a x = b and x >= 0. An error if the tableau overflows.
+pub(super) fn minimise(a: &[Vec<Q>], b: &[Q], cost: &[Q]) -> Result<LpResult, String> {
+    let (m, n) = (a.len(), cost.len());
+    // Start from an artificial variable per row, with rows negated so that b >= 0.
+    let rows = (0..m)
+        .map(|i| {
+            let sign = if b[i] < Q::ZERO { -Q::ONE } else { Q::ONE };
+            let row: Option<Vec<Q>> = a[i].iter().map(|&x| x.checked_mul(sign)).collect();
+            let mut row = row.ok_or_else(overflow)?;
+            row.extend((0..m).map(|k| if k == i { Q::ONE } else { Q::ZERO }));
+            row.push(b[i].checked_mul(sign).ok_or_else(overflow)?);
+            Ok(row)
+        })
+        .collect::<Result<_, String>>()?;
+    let mut tableau = Tableau {
+        rows,
+        basis: (n..n + m).collect(),
+        rhs: n + m,
+    };
+
+    // Phase 1: minimise the sum of the artificial variables.
+    let mut phase1 = vec![Q::ZERO; n];
+    phase1.extend(vec![Q::ONE; m]);
+    tableau.optimise(&phase1, n + m)?;
+    if (0..m).any(|i| tableau.basis[i] >= n && tableau.rows[i][n + m] != Q::ZERO) {
+        return Ok(LpResult::Infeasible);
+    }
+    // Pivot the remaining artificial variables out of the basis where possible.
+    // Rows where that fails are redundant and no longer change.
+    for i in 0..m {
+        if tableau.basis[i] >= n {
+            if let Some(j) = (0..n).find(|&j| tableau.rows[i][j] != Q::ZERO) {
+                tableau.pivot(i, j)?;
+            }
+        }
+    }
+
+    // Phase 2: the actual objective, never letting artificial variables back in.
+    let mut phase2 = cost.to_vec();
+    phase2.extend(vec![Q::ZERO; m]);
+    if !tableau.optimise(&phase2, n)? {
+        return Ok(LpResult::Unbounded);
+    }
+    let mut x = vec![Q::ZERO; n];
+    for (row, &b) in tableau.rows.iter().zip(&tableau.basis) {
+        if b < n {
+            x[b] = row[n + m];
+        }
+    }
+    return Ok(LpResult::Optimal(x));
+}
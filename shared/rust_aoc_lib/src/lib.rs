@@ -1,6 +1,18 @@
+// Explicit returns and `module/module.rs` layouts are the house style.
+#![allow(clippy::needless_return, clippy::module_inception)]
+
 pub mod graph;
+pub mod grid;
+pub mod ilp;
+pub mod matrix;
+pub mod modular;
 pub mod multivec;
 pub mod complex;
+pub mod num;
+pub mod point;
+pub mod primes;
+pub mod rational;
+pub mod sat;
 pub mod utility_functions;
 
 pub use utility_functions as utils;
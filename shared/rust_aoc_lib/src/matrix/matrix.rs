@@ -0,0 +1,196 @@
+use crate::num::Num;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut, Mul};
+
+// Dense matrix for small linear systems.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Matrix<T> {
+    // Entries stored in row-major order: entry (i, j) is data[i * cols + j].
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Matrix<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    // Panics if the rows are not all of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "Matrix rows must have equal length."
+        );
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn identity(n: usize) -> Self
+    where
+        T: Num,
+    {
+        let mut matrix = Self::new(n, n, T::ZERO);
+        for i in 0..n {
+            matrix[(i, i)] = T::ONE;
+        }
+        return matrix;
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // All entries in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> Vec<T>
+    where
+        T: Clone,
+    {
+        (0..self.rows).map(|i| self[(i, j)].clone()).collect()
+    }
+
+    pub fn swap_rows(&mut self, i: usize, k: usize) {
+        for j in 0..self.cols {
+            self.data.swap(i * self.cols + j, k * self.cols + j);
+        }
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: (0..self.cols).flat_map(|j| self.column(j)).collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    pub fn map<V>(&self, fun: &dyn Fn(&T) -> V) -> Matrix<V> {
+        Matrix {
+            data: self.data.iter().map(fun).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    // The matrix-vector product self * x. Panics if the sizes do not match.
+    pub fn apply(&self, x: &[T]) -> Vec<T>
+    where
+        T: Num,
+    {
+        assert_eq!(x.len(), self.cols, "Vector length must match the columns.");
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(x)
+                    .fold(T::ZERO, |acc, (&a, &b)| acc + a * b)
+            })
+            .collect()
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({i}, {j}) out of bounds."
+        );
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({i}, {j}) out of bounds."
+        );
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+// Matrix product. Panics if the inner dimensions do not match.
+impl<T> Mul for &Matrix<T>
+where
+    T: Num,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Self) -> Matrix<T> {
+        assert_eq!(self.cols, other.rows, "Matrix dimensions do not match.");
+        let mut product = Matrix::new(self.rows, other.cols, T::ZERO);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                for j in 0..other.cols {
+                    product[(i, j)] = product[(i, j)] + self[(i, k)] * other[(k, j)];
+                }
+            }
+        }
+        return product;
+    }
+}
+
+impl<T> Display for Matrix<T>
+where
+    T: Display,
+{
+    // One line per row, with entries separated by spaces.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for i in 0..self.rows {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row: Vec<String> = self.row(i).iter().map(|x| x.to_string()).collect();
+            write!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products() {
+        let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = a.transpose();
+        assert_eq!((b.rows(), b.cols()), (3, 2));
+        assert_eq!(b.as_slice(), &[1, 4, 2, 5, 3, 6]);
+        assert_eq!(b.column(1), vec![4, 5, 6]);
+        assert_eq!((&a * &b).to_string(), "14 32\n32 77");
+        assert_eq!(&a * &Matrix::identity(3), a);
+        assert_eq!(a.apply(&[1, 0, -1]), vec![-2, -2]);
+
+        let mut c = a.map(&|x| x * 10);
+        c.swap_rows(0, 1);
+        assert_eq!(c.row(0), &[40, 50, 60]);
+    }
+}
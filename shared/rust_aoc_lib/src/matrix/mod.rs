@@ -0,0 +1,5 @@
+mod matrix;
+mod solve;
+
+pub use matrix::*;
+pub use solve::*;
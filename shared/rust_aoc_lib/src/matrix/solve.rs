@@ -0,0 +1,391 @@
+// Exact linear algebra over the integers. Elimination is fraction-free (Bareiss),
+// so every intermediate entry is a minor of the input and divisions are exact;
+// only back-substitution uses rationals.
+
+use super::Matrix;
+use crate::rational::Rational;
+
+type Q = Rational<i128>;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Solution {
+    Unique(Vec<Q>),
+    // All solutions are particular plus any linear combination of the null space basis.
+    Infinite {
+        particular: Vec<Q>,
+        null_space: Vec<Vec<Q>>,
+    },
+    Inconsistent,
+}
+
+// Row echelon form of the input in i128, with its pivot columns.
+struct Echelon {
+    matrix: Matrix<i128>,
+    pivots: Vec<usize>,
+    // Whether an odd number of row swaps was made.
+    odd_swaps: bool,
+}
+
+// (p a - f b) / d for an exact division, with the products taken in 256 bits so
+// that only the quotient has to fit in i128. None if it does not.
+fn cross_div(p: i128, a: i128, f: i128, b: i128, d: i128) -> Option<i128> {
+    if let (Some(x), Some(y)) = (p.checked_mul(a), f.checked_mul(b)) {
+        if let Some(z) = x.checked_sub(y) {
+            return Some(z / d);
+        }
+    }
+    let (hi, lo) = wide_sub(wide_mul(p, a), wide_mul(f, b));
+    let negative = (hi as i128) < 0;
+    let (hi, lo) = if negative {
+        wide_neg((hi, lo))
+    } else {
+        (hi, lo)
+    };
+    let divisor = d.unsigned_abs();
+    if hi >= divisor {
+        return None;
+    }
+    // Long division, one bit of lo at a time.
+    let (mut rem, mut quotient) = (hi, 0_u128);
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        if carry == 1 || rem >= divisor {
+            rem = rem.wrapping_sub(divisor);
+            quotient |= 1 << i;
+        }
+    }
+    let quotient = i128::try_from(quotient).ok()?;
+    return Some(if negative != (d < 0) {
+        -quotient
+    } else {
+        quotient
+    });
+}
+
+// A 256-bit two's complement integer as (high, low) halves.
+type Wide = (u128, u128);
+
+fn wide_mul(a: i128, b: i128) -> Wide {
+    const LOW: u128 = u64::MAX as u128;
+    let (x, y) = (a.unsigned_abs(), b.unsigned_abs());
+    let (x1, x0, y1, y0) = (x >> 64, x & LOW, y >> 64, y & LOW);
+    let (p00, p01, p10, p11) = (x0 * y0, x0 * y1, x1 * y0, x1 * y1);
+    let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+    let product = (
+        p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+        (p00 & LOW) | (mid << 64),
+    );
+    return if (a < 0) != (b < 0) {
+        wide_neg(product)
+    } else {
+        product
+    };
+}
+
+fn wide_neg((hi, lo): Wide) -> Wide {
+    ((!hi).wrapping_add((lo == 0) as u128), (!lo).wrapping_add(1))
+}
+
+fn wide_sub((h1, l1): Wide, (h2, l2): Wide) -> Wide {
+    (
+        h1.wrapping_sub(h2).wrapping_sub((l1 < l2) as u128),
+        l1.wrapping_sub(l2),
+    )
+}
+
+// Eliminate below the pivots in the first cols columns, carrying along any others.
+// Fails if a minor of the input does not fit in i128.
+fn bareiss(mut m: Matrix<i128>, cols: usize) -> Result<Echelon, String> {
+    let (mut pivots, mut odd_swaps) = (vec![], false);
+    let mut previous = 1;
+    for c in 0..cols {
+        let r = pivots.len();
+        let Some(p) = (r..m.rows()).find(|&i| m[(i, c)] != 0) else {
+            continue;
+        };
+        if p != r {
+            m.swap_rows(p, r);
+            odd_swaps = !odd_swaps;
+        }
+        let pivot = m[(r, c)];
+        for i in r + 1..m.rows() {
+            let factor = m[(i, c)];
+            for j in c + 1..m.cols() {
+                m[(i, j)] = cross_div(pivot, m[(i, j)], factor, m[(r, j)], previous)
+                    .ok_or("Overflow in elimination.")?;
+            }
+            m[(i, c)] = 0;
+        }
+        previous = pivot;
+        pivots.push(c);
+    }
+    return Ok(Echelon {
+        matrix: m,
+        pivots,
+        odd_swaps,
+    });
+}
+
+fn to_i128<T: Copy + Into<i128>>(a: &Matrix<T>) -> Matrix<i128> {
+    a.map(&|&x| x.into())
+}
+
+// Panics if the matrix is not square. Fails if a minor overflows i128.
+pub fn determinant<T: Copy + Into<i128>>(a: &Matrix<T>) -> Result<i128, String> {
+    assert_eq!(a.rows(), a.cols(), "Determinant of a non-square matrix.");
+    let echelon = bareiss(to_i128(a), a.cols())?;
+    if echelon.pivots.len() < a.rows() {
+        return Ok(0);
+    }
+    // The last pivot of the fraction-free elimination is the determinant itself.
+    let det = if a.rows() == 0 {
+        1
+    } else {
+        echelon.matrix[(a.rows() - 1, a.cols() - 1)]
+    };
+    return Ok(if echelon.odd_swaps { -det } else { det });
+}
+
+pub fn rank<T: Copy + Into<i128>>(a: &Matrix<T>) -> Result<usize, String> {
+    Ok(bareiss(to_i128(a), a.cols())?.pivots.len())
+}
+
+// Solve a x = b exactly. Panics if b does not have one entry per row. Fails if a
+// minor of the augmented matrix or a value in back-substitution overflows i128;
+// minors grow with the size of the system, so keep systems small, e.g. by solving
+// for some of the unknowns first.
+pub fn solve<T: Copy + Into<i128>>(a: &Matrix<T>, b: &[T]) -> Result<Solution, String> {
+    assert_eq!(b.len(), a.rows(), "Right-hand side must match the rows.");
+    let mut augmented = Matrix::new(a.rows(), a.cols() + 1, 0);
+    for i in 0..a.rows() {
+        for j in 0..a.cols() {
+            augmented[(i, j)] = a[(i, j)].into();
+        }
+        augmented[(i, a.cols())] = b[i].into();
+    }
+    let Echelon { matrix, pivots, .. } = bareiss(augmented, a.cols())?;
+    if (pivots.len()..a.rows()).any(|i| matrix[(i, a.cols())] != 0) {
+        return Ok(Solution::Inconsistent);
+    }
+
+    // Solve for the pivot variables given values of the free ones, bottom row first.
+    let back_substitute = |mut x: Vec<Q>, rhs: bool| {
+        for (r, &c) in pivots.iter().enumerate().rev() {
+            let mut value = if rhs {
+                Q::from(matrix[(r, a.cols())])
+            } else {
+                Q::ZERO
+            };
+            for j in c + 1..a.cols() {
+                value = (x[j].checked_mul(Q::from(matrix[(r, j)])))
+                    .and_then(|term| value.checked_sub(term))
+                    .ok_or("Overflow in back-substitution.")?;
+            }
+            x[c] = (value.checked_div(Q::from(matrix[(r, c)])))
+                .ok_or("Overflow in back-substitution.")?;
+        }
+        return Ok(x);
+    };
+
+    let particular = back_substitute(vec![Q::ZERO; a.cols()], true)?;
+    let free: Vec<usize> = (0..a.cols()).filter(|c| !pivots.contains(c)).collect();
+    if free.is_empty() {
+        return Ok(Solution::Unique(particular));
+    }
+    let null_space = free
+        .iter()
+        .map(|&f| {
+            let mut x = vec![Q::ZERO; a.cols()];
+            x[f] = Q::ONE;
+            back_substitute(x, false)
+        })
+        .collect::<Result<_, String>>()?;
+    return Ok(Solution::Infinite {
+        particular,
+        null_space,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point3;
+
+    fn integers(x: &[Q]) -> Vec<i128> {
+        x.iter().map(|q| q.to_integer().unwrap()).collect()
+    }
+
+    #[test]
+    fn determinants() {
+        let a = Matrix::from_rows(vec![vec![2, -1, 0], vec![1, 3, 2], vec![0, 1, 1]]);
+        assert_eq!(determinant(&a), Ok(3));
+        assert_eq!(determinant(&a.transpose()), Ok(3));
+        assert_eq!(
+            determinant(&Matrix::from_rows(vec![vec![0, 1], vec![1, 0]])),
+            Ok(-1)
+        );
+        assert_eq!(determinant(&Matrix::<i64>::identity(4)), Ok(1));
+
+        let singular = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        assert_eq!(determinant(&singular), Ok(0));
+        assert_eq!(rank(&singular), Ok(2));
+        assert_eq!(rank(&Matrix::new(2, 5, 0)), Ok(0));
+    }
+
+    #[test]
+    fn claw_machine() {
+        // Example from year 2024 day 13, with the prize moved by 10^13 as in part 2.
+        let a = Matrix::from_rows(vec![vec![94_i64, 22], vec![34, 67]]);
+        assert_eq!(
+            solve(&a, &[8400, 5400]),
+            Ok(Solution::Unique(vec![Q::from(80), Q::from(40)]))
+        );
+        let offset = 10_000_000_000_000;
+        let a = Matrix::from_rows(vec![vec![26_i64, 67], vec![66, 21]]);
+        let Solution::Unique(x) = solve(&a, &[offset + 12748, offset + 12176]).unwrap() else {
+            panic!("Expected a unique solution.");
+        };
+        assert_eq!(integers(&x), vec![118679050709, 103199174542]);
+    }
+
+    #[test]
+    fn hailstones() {
+        // Example from year 2023 day 24. A rock p + t v hitting hailstone p_i + t v_i
+        // satisfies (p - p_i) x (v - v_i) = 0. Subtracting this for two hailstones cancels
+        // the quadratic term p x v: p x (v_j - v_i) + (p_j - p_i) x v = p_j x v_j - p_i x v_i.
+        let stones = [
+            (Point3::new(19, 13, 30), Point3::new(-2, 1, -2)),
+            (Point3::new(18, 19, 22), Point3::new(-1, -1, -2)),
+            (Point3::new(20, 25, 34), Point3::new(-2, -2, -4)),
+        ];
+        // The matrix of u x (.).
+        let skew = |u: Point3<i64>| [[0, -u.z, u.y], [u.z, 0, -u.x], [-u.y, u.x, 0]];
+        let (mut rows, mut rhs) = (vec![], vec![]);
+        for (pj, vj) in &stones[1..] {
+            let (pi, vi) = stones[0];
+            let (dv, dp) = (skew(*vj - vi), skew(*pj - pi));
+            let c = pj.cross(vj) - pi.cross(&vi);
+            for k in 0..3 {
+                rows.push([dv[k].map(|x| -x), dp[k]].concat());
+            }
+            rhs.extend([c.x, c.y, c.z]);
+        }
+        let Solution::Unique(x) = solve(&Matrix::from_rows(rows), &rhs).unwrap() else {
+            panic!("Expected a unique solution.");
+        };
+        assert_eq!(integers(&x), vec![24, 13, 10, -3, 1, 2]);
+    }
+
+    #[test]
+    fn hailstones_at_scale() {
+        // Year 2023 day 24 at the magnitude of real inputs, with hailstones made to
+        // meet a known rock at large times.
+        let rock = (
+            Point3::new(135526954892228_i64, 166379815072828, 365851816237219),
+            Point3::new(88, -86, -204),
+        );
+        let hits = [
+            (527419563041, Point3::new(-43, 271, 115)),
+            (308552210978, Point3::new(211, -19, -247)),
+            (781306444302, Point3::new(17, -163, -58)),
+            (164023577815, Point3::new(-290, 54, 36)),
+            (639890010526, Point3::new(102, 240, -131)),
+        ];
+        let stones: Vec<_> = hits
+            .iter()
+            .map(|&(t, v)| (rock.0 + (rock.1 - v) * t, v))
+            .collect();
+
+        // The full 6x6 system has minors of about 10^50, beyond i128.
+        let skew = |u: Point3<i64>| [[0, -u.z, u.y], [u.z, 0, -u.x], [-u.y, u.x, 0]];
+        let (mut rows, mut rhs) = (vec![], vec![]);
+        for (pj, vj) in &stones[1..3] {
+            let (pi, vi) = stones[0];
+            let (dv, dp) = (skew(*vj - vi), skew(*pj - pi));
+            let c = pj.cross(vj) - pi.cross(&vi);
+            for k in 0..3 {
+                rows.push([dv[k].map(|x| -x), dp[k]].concat());
+            }
+            rhs.extend([c.x, c.y, c.z]);
+        }
+        assert!(solve(&Matrix::from_rows(rows), &rhs).is_err());
+
+        // Two coordinates at a time, the rock (p, v) satisfies the 4x4 system
+        // (p - p_i) x (v - v_i) = 0 minus the same for hailstone 0, in p_a, p_b, v_a, v_b.
+        let coordinate = |p: Point3<i64>, axis: usize| [p.x, p.y, p.z][axis];
+        let plane = |a: usize, b: usize| {
+            let (mut rows, mut rhs) = (vec![], vec![]);
+            let (p0, v0) = stones[0];
+            let [p0a, p0b, v0a, v0b] = [
+                coordinate(p0, a),
+                coordinate(p0, b),
+                coordinate(v0, a),
+                coordinate(v0, b),
+            ];
+            for &(p, v) in &stones[1..] {
+                let [pa, pb, va, vb] = [
+                    coordinate(p, a),
+                    coordinate(p, b),
+                    coordinate(v, a),
+                    coordinate(v, b),
+                ];
+                rows.push(vec![v0b - vb, va - v0a, pb - p0b, p0a - pa]);
+                rhs.push(p0a * v0b - p0b * v0a - (pa * vb - pb * va));
+            }
+            let Solution::Unique(x) = solve(&Matrix::from_rows(rows), &rhs).unwrap() else {
+                panic!("Expected a unique solution.");
+            };
+            return integers(&x);
+        };
+        let (xy, xz) = (plane(0, 1), plane(0, 2));
+        assert_eq!(
+            [xy[0], xy[1], xz[1], xy[2], xy[3], xz[3]],
+            [
+                135526954892228,
+                166379815072828,
+                365851816237219,
+                88,
+                -86,
+                -204
+            ]
+        );
+    }
+
+    #[test]
+    fn wide_arithmetic() {
+        let big = 10_i128.pow(30);
+        assert_eq!(cross_div(big, big, big, big - 1, big / 10), Some(10));
+        assert_eq!(cross_div(big, -big, -big, big - 1, big / 10), Some(-10));
+        assert_eq!(cross_div(-big, big, big, -big, -7), Some(0));
+        assert_eq!(cross_div(big, big, 0, 0, 1), None);
+        assert_eq!(cross_div(6, 7, 2, 3, -4), Some(-9));
+    }
+
+    #[test]
+    fn degenerate_systems() {
+        // x + y + z = 6 and x - y = 0.
+        let a = Matrix::from_rows(vec![vec![1, 1, 1], vec![1, -1, 0]]);
+        let Solution::Infinite {
+            particular,
+            null_space,
+        } = solve(&a, &[6, 0]).unwrap()
+        else {
+            panic!("Expected infinitely many solutions.");
+        };
+        assert_eq!(integers(&particular), vec![3, 3, 0]);
+        assert_eq!(null_space, vec![vec![Q::new(-1, 2), Q::new(-1, 2), Q::ONE]]);
+
+        let a = Matrix::from_rows(vec![vec![1, 1], vec![2, 2]]);
+        assert_eq!(solve(&a, &[1, 3]), Ok(Solution::Inconsistent));
+        assert!(matches!(solve(&a, &[1, 2]), Ok(Solution::Infinite { .. })));
+
+        // More equations than unknowns.
+        let a = Matrix::from_rows(vec![vec![1, 0], vec![0, 2], vec![1, 1]]);
+        assert_eq!(
+            solve(&a, &[1, 4, 3]),
+            Ok(Solution::Unique(vec![Q::ONE, Q::from(2)]))
+        );
+        assert_eq!(solve(&a, &[1, 4, 4]), Ok(Solution::Inconsistent));
+    }
+}
@@ -0,0 +1,5 @@
+mod modint;
+mod modular;
+
+pub use modint::*;
+pub use modular::*;
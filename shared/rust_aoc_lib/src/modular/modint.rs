@@ -0,0 +1,282 @@
+use super::mod_inverse;
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Residue modulo the compile-time modulus M, where 0 < M < 2^63 is checked at compile time.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Copy, Clone)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+// Residue modulo a modulus chosen at runtime. Operations on residues with different
+// moduli panic, as do sums and products of no residues, which have no modulus.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: i64) -> Self {
+        const { assert!(M > 0 && M < 1 << 63, "Modulus must be in 1..2^63.") }
+        Self {
+            value: (value as i128).rem_euclid(M as i128) as u64,
+        }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        M
+    }
+
+    fn with_value(self, value: u64) -> Self {
+        Self { value }
+    }
+
+    // The modulus is part of the type, so residues always match.
+    fn check(&self, _other: &Self) {}
+
+    fn identity(value: u64) -> Option<Self> {
+        Some(Self::from(value))
+    }
+}
+
+impl DynModInt {
+    // Panics if modulus is zero. As for ModInt, division and inverses need modulus < 2^63.
+    pub fn new(value: i64, modulus: u64) -> Self {
+        assert!(modulus > 0, "Modulus must be positive.");
+        Self {
+            value: (value as i128).rem_euclid(modulus as i128) as u64,
+            modulus,
+        }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    fn with_value(self, value: u64) -> Self {
+        Self { value, ..self }
+    }
+
+    fn check(&self, other: &Self) {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "Residues with different moduli."
+        );
+    }
+
+    // Without any residue there is no modulus to take value modulo.
+    fn identity(_value: u64) -> Option<Self> {
+        None
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        const { assert!(M > 0 && M < 1 << 63, "Modulus must be in 1..2^63.") }
+        Self { value: value % M }
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(value: i64) -> Self {
+        Self::new(value)
+    }
+}
+
+// Everything else is shared, with the modulus from the modulus method.
+macro_rules! impl_modint {
+    ([$($generics:tt)*] $t:ty) => {
+        impl<$($generics)*> $t {
+            // Representative in 0..modulus.
+            pub fn value(&self) -> u64 {
+                self.value
+            }
+
+            pub fn pow(self, mut k: u64) -> Self {
+                let (mut base, mut result) = (self, self.with_value(1 % self.modulus()));
+                while k > 0 {
+                    if k & 1 == 1 {
+                        result *= base;
+                    }
+                    base *= base;
+                    k >>= 1;
+                }
+                return result;
+            }
+
+            // None unless the value is coprime to the modulus.
+            pub fn inverse(self) -> Option<Self> {
+                let inverse = mod_inverse(self.value as i64, self.modulus() as i64)?;
+                Some(self.with_value(inverse as u64))
+            }
+        }
+
+        impl<$($generics)*> Add for $t {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                self.check(&other);
+                let sum = (self.value as u128 + other.value as u128) % self.modulus() as u128;
+                self.with_value(sum as u64)
+            }
+        }
+
+        impl<$($generics)*> Sub for $t {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                self + (-other)
+            }
+        }
+
+        impl<$($generics)*> Mul for $t {
+            type Output = Self;
+
+            fn mul(self, other: Self) -> Self {
+                self.check(&other);
+                let product = self.value as u128 * other.value as u128 % self.modulus() as u128;
+                self.with_value(product as u64)
+            }
+        }
+
+        // Panics if other is not invertible.
+        impl<$($generics)*> Div for $t {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, other: Self) -> Self {
+                self * other.inverse().expect("Division by a non-invertible residue.")
+            }
+        }
+
+        impl<$($generics)*> Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                let m = self.modulus();
+                self.with_value((m - self.value) % m)
+            }
+        }
+
+        impl<$($generics)*> AddAssign for $t {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<$($generics)*> SubAssign for $t {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<$($generics)*> MulAssign for $t {
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        impl<$($generics)*> DivAssign for $t {
+            fn div_assign(&mut self, other: Self) {
+                *self = *self / other;
+            }
+        }
+
+        // Empty sums and products need the modulus from the type.
+        impl<$($generics)*> Sum for $t {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.reduce(|acc, x| acc + x)
+                    .or_else(|| Self::identity(0))
+                    .expect("Empty sum of residues without a modulus.")
+            }
+        }
+
+        impl<$($generics)*> Product for $t {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.reduce(|acc, x| acc * x)
+                    .or_else(|| Self::identity(1))
+                    .expect("Empty product of residues without a modulus.")
+            }
+        }
+
+        impl<$($generics)*> Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.value)
+            }
+        }
+    };
+}
+
+impl_modint!([const M: u64] ModInt<M>);
+impl_modint!([] DynModInt);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 1_000_000_007;
+    type Mint = ModInt<P>;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Mint::new(-1), Mint::from(123456789_u64));
+        assert_eq!(a.value(), P - 1);
+        assert_eq!(a + Mint::new(1), Mint::new(0));
+        assert_eq!((a * a).value(), 1);
+        assert_eq!(a / b * b, a);
+        assert_eq!(b.pow(P - 1), Mint::new(1));
+        assert_eq!(b.inverse().unwrap(), b.pow(P - 2));
+        assert_eq!((1..=10).map(Mint::new).sum::<Mint>(), Mint::new(55));
+        assert_eq!(std::iter::empty().product::<Mint>(), Mint::new(1));
+        assert_eq!((1..=20).map(Mint::new).product::<Mint>().value(), 146326063);
+        assert_eq!(ModInt::<10>::new(4).inverse(), None);
+        assert_eq!(ModInt::<1>::new(5).pow(0), ModInt::new(0));
+
+        let mut c = Mint::new(10);
+        c -= Mint::new(20);
+        c *= Mint::new(3);
+        c /= Mint::new(-30);
+        c += Mint::new(1);
+        assert_eq!(c.to_string(), "2");
+    }
+
+    #[test]
+    fn card_shuffle() {
+        // Example from year 2019 day 22: deal with increment 7, then twice into a new stack.
+        // Each technique maps a card's position p to a * p + b.
+        let techniques = [(7, 0), (-1, -1), (-1, -1)];
+        let (a, b) = techniques
+            .iter()
+            .fold((ModInt::<10>::new(1), ModInt::new(0)), |(a, b), &(c, d)| {
+                (a * ModInt::new(c), b * ModInt::new(c) + ModInt::new(d))
+            });
+        // The card at position q is the one mapped there.
+        let inverse = a.inverse().unwrap();
+        let deck: Vec<u64> = (0..10)
+            .map(|q| ((ModInt::new(q) - b) * inverse).value())
+            .collect();
+        assert_eq!(deck, vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]);
+    }
+
+    #[test]
+    fn runtime_modulus() {
+        let m = 20201227;
+        let (a, b) = (DynModInt::new(7, m), DynModInt::new(-3, m));
+        assert_eq!(a.pow(8).value(), 5764801);
+        assert_eq!((a - b).value(), 10);
+        assert_eq!(a / b * b, a);
+        assert_eq!(b.modulus(), m);
+        assert_eq!(DynModInt::new(6, 9).inverse(), None);
+        let residues: Vec<DynModInt> = (1..=4).map(|k| DynModInt::new(k, 7)).collect();
+        assert_eq!(residues.iter().copied().sum::<DynModInt>().value(), 3);
+        assert_eq!(residues.into_iter().product::<DynModInt>().value(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "different moduli")]
+    fn mixed_moduli() {
+        let _ = DynModInt::new(1, 5) + DynModInt::new(1, 7);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::utils::bezout;
+use std::collections::HashMap;
+
+// base^exp mod m, by repeated squaring with 128-bit products.
+pub fn pow_mod(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = m as u128;
+    let (mut base, mut result) = (base as u128 % m, 1 % m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    return result as u64;
+}
+
+// x in 0..m with a * x = 1 mod m, None unless a and m are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, s, _) = bezout(a.rem_euclid(m), m);
+    (g == 1).then_some(s.rem_euclid(m))
+}
+
+// Smallest k >= 0 with g^k = h mod m, by baby-step giant-step in O(sqrt(m)).
+// May miss solutions unless g and m are coprime.
+pub fn discrete_log(g: u64, h: u64, m: u64) -> Option<u64> {
+    let h = h % m;
+    if h == 1 % m {
+        return Some(0);
+    }
+    let n = (m as f64).sqrt().ceil() as u64;
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % m as u128) as u64;
+
+    // Baby steps: h * g^j for 0 <= j < n, keeping the largest j per value.
+    let mut baby = HashMap::new();
+    let mut value = h;
+    for j in 0..n {
+        baby.insert(value, j);
+        value = mul(value, g);
+    }
+    // Giant steps: g^(i n) = h * g^j gives k = i n - j, with the smallest i first.
+    let giant = pow_mod(g, n, m);
+    let mut value = 1 % m;
+    for i in 1..=n {
+        value = mul(value, giant);
+        if let Some(&j) = baby.get(&value) {
+            return Some(i * n - j);
+        }
+    }
+    return None;
+}
+
+// The smaller square root x with x^2 = a mod p for a prime p, by Tonelli-Shanks.
+// None if a is not a square.
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    // Euler's criterion.
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % p as u128) as u64;
+
+    // p - 1 = q 2^s with q odd, and z a non-square.
+    let (mut q, mut s) = (p - 1, 0);
+    while q % 2 == 0 {
+        (q, s) = (q / 2, s + 1);
+    }
+    let z = (2..p)
+        .find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)
+        .unwrap();
+
+    let (mut m, mut c) = (s, pow_mod(z, q, p));
+    let (mut t, mut r) = (pow_mod(a, q, p), pow_mod(a, q.div_ceil(2), p));
+    while t != 1 {
+        // Least i with t^(2^i) = 1.
+        let (mut i, mut t2) = (0, t);
+        while t2 != 1 {
+            (i, t2) = (i + 1, mul(t2, t2));
+        }
+        let b = pow_mod(c, 1 << (m - i - 1), p);
+        (m, c) = (i, mul(b, b));
+        (t, r) = (mul(t, c), mul(r, b));
+    }
+    return Some(r.min(p - r));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn powers_and_inverses() {
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(-3, 7), Some(2));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn discrete_logarithms() {
+        // Example from year 2020 day 25: loop sizes of the card and the door.
+        let m = 20201227;
+        assert_eq!(discrete_log(7, 5764801, m), Some(8));
+        assert_eq!(discrete_log(7, 17807724, m), Some(11));
+        assert_eq!(pow_mod(17807724, 8, m), 14897079);
+
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(2, 1024, 1_000_000_007), Some(10));
+        for h in 1..13 {
+            let k = discrete_log(2, h, 13).unwrap();
+            assert_eq!(pow_mod(2, k, 13), h);
+            assert!(k < 12);
+        }
+    }
+
+    #[test]
+    fn square_roots() {
+        assert_eq!(sqrt_mod(10, 13), Some(6));
+        assert_eq!(sqrt_mod(5, 13), None);
+        assert_eq!(sqrt_mod(0, 13), Some(0));
+        assert_eq!(sqrt_mod(1, 2), Some(1));
+
+        // p - 1 = 119 * 2^23 has many factors 2, which the main loop works through.
+        let p = 998_244_353;
+        for a in [2, 3, 5, 123456789, p - 1] {
+            if let Some(x) = sqrt_mod(a, p) {
+                assert_eq!(pow_mod(x, 2, p), a);
+                assert!(x <= p - x);
+            }
+        }
+        let squares = (1..100).filter(|&a| sqrt_mod(a, p).is_some()).count();
+        assert!(squares > 10 && squares < 90);
+    }
+}
@@ -1,6 +1,6 @@
 mod multivec;
-mod scalar;
+mod multivec3d;
 mod scalar_operations;
 
 pub use multivec::*;
-pub use scalar::*;
\ No newline at end of file
+pub use multivec3d::*;
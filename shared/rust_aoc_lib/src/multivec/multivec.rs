@@ -1,4 +1,4 @@
-use super::Scalar;
+use crate::num::Num;
 use std::fmt::{Debug, Display};
 use std::iter::zip;
 use std::ops::{Add, Div, Mul, Sub, Neg};
@@ -10,7 +10,7 @@ pub struct Multivec2D<T> {
 
 impl<T> Multivec2D<T>
 where
-    T: Scalar<T> + PartialEq,
+    T: Num,
 {
     pub const E1: Self = Self {
         data: [T::ZERO, T::ONE, T::ZERO, T::ZERO],
@@ -65,7 +65,7 @@ where
 
 impl<T> From<T> for Multivec2D<T>
 where
-    T: Scalar<T>,
+    T: Num,
 {
     fn from(value: T) -> Self {
         Self {
@@ -76,7 +76,7 @@ where
 
 impl<T> From<[T; 2]> for Multivec2D<T>
 where
-    T: Scalar<T> + Copy,
+    T: Num,
 {
     fn from(arr: [T; 2]) -> Self {
         Self {
@@ -117,7 +117,7 @@ where
 
 impl<T> Add<T> for Multivec2D<T>
 where
-    T: Scalar<T> + Debug + Add<Output = T>,
+    T: Num,
 {
     type Output = Self;
 
@@ -213,7 +213,7 @@ where
 
 impl<T> Neg for Multivec2D<T>
 where
-    T: Scalar<T> + Neg<Output = T> + Mul<Output = T> + Debug + Copy,
+    T: Num + Neg<Output = T>,
 {
     type Output = Self;
 
@@ -250,6 +250,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn integer_widths() {
+        let e1 = Multivec2D::<i32>::E1;
+        let e2 = Multivec2D::<i32>::E2;
+        assert_eq!(e1 * e2, Multivec2D::<i32>::I);
+        assert_eq!((e1 + e2) * 3, Multivec2D::vector(3, 3));
+    }
+
     #[test]
     fn complex_numbers() {
         let i = Multivec2D::<i64>::I;
@@ -0,0 +1,449 @@
+use crate::num::Num;
+use crate::point::Point3;
+use std::fmt::{Debug, Display};
+use std::iter::zip;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Multivectors of the 3D Euclidean geometric algebra, with components in the order
+// 1, e1, e2, e3, e23, e31, e12, e123. The bivector basis is chosen such that the
+// wedge product of two vectors has the same components as their cross product.
+#[derive(Debug, PartialEq, Eq, Hash, Default, Copy, Clone)]
+pub struct Multivec3D<T> {
+    pub data: [T; 8],
+}
+
+// Each component as a bitmask of the basis vectors in canonical order e1 < e2 < e3,
+// and the sign relating it to that order, since e31 = -e1 e3.
+const BLADES: [usize; 8] = [0b000, 0b001, 0b010, 0b100, 0b110, 0b101, 0b011, 0b111];
+const ORIENTATION: [bool; 8] = [true, true, true, true, true, false, true, true];
+const GRADES: [usize; 8] = [0, 1, 1, 1, 2, 2, 2, 3];
+
+// Index of the component with the given blade bitmask.
+fn blade_index(blade: usize) -> usize {
+    BLADES.iter().position(|&b| b == blade).unwrap()
+}
+
+// Whether the product of canonical blades a and b is +(a ^ b), rather than -(a ^ b),
+// counting the swaps needed to bring the basis vectors into canonical order.
+fn reorder_sign(a: usize, b: usize) -> bool {
+    let (mut a, mut swaps) = (a >> 1, 0);
+    while a != 0 {
+        swaps += (a & b).count_ones();
+        a >>= 1;
+    }
+    return swaps % 2 == 0;
+}
+
+impl<T> Multivec3D<T>
+where
+    T: Num,
+{
+    pub const ZERO: Self = Self { data: [T::ZERO; 8] };
+    pub const E1: Self = Self::basis(1);
+    pub const E2: Self = Self::basis(2);
+    pub const E3: Self = Self::basis(3);
+    // Unit pseudoscalar e123.
+    pub const I: Self = Self::basis(7);
+
+    const fn basis(k: usize) -> Self {
+        let mut data = [T::ZERO; 8];
+        data[k] = T::ONE;
+        Self { data }
+    }
+
+    pub fn vector(x: T, y: T, z: T) -> Self {
+        Self {
+            data: [T::ZERO, x, y, z, T::ZERO, T::ZERO, T::ZERO, T::ZERO],
+        }
+    }
+
+    // Bivector with components along e23, e31 and e12, i.e. the plane with normal (x, y, z).
+    pub fn bivector(x: T, y: T, z: T) -> Self {
+        Self {
+            data: [T::ZERO, T::ZERO, T::ZERO, T::ZERO, x, y, z, T::ZERO],
+        }
+    }
+
+    // Components (e1, e2, e3) of the vector part.
+    pub fn vector_part(&self) -> [T; 3] {
+        [self.data[1], self.data[2], self.data[3]]
+    }
+
+    // Components (e23, e31, e12) of the bivector part.
+    pub fn bivector_part(&self) -> [T; 3] {
+        [self.data[4], self.data[5], self.data[6]]
+    }
+
+    pub fn grade_project(mut self, grade: usize) -> Self {
+        assert!(grade <= 3, "Grade {grade} is out of bounds.");
+        for (k, value) in self.data.iter_mut().enumerate() {
+            if GRADES[k] != grade {
+                *value = T::ZERO;
+            }
+        }
+        return self;
+    }
+
+    pub fn grade(&self) -> Option<usize> {
+        // If homogenous; return the grade, otherwise return None.
+        let mut grades = (0..8)
+            .filter(|&k| self.data[k] != T::ZERO)
+            .map(|k| GRADES[k]);
+        let first = grades.next().unwrap_or(0);
+        grades.all(|g| g == first).then_some(first)
+    }
+}
+
+// Products pick up signs from reordering basis vectors, so they need signed components.
+impl<T> Multivec3D<T>
+where
+    T: Num + Neg<Output = T>,
+{
+    // Sum of the products of all pairs of components for which keep(blade a, blade b) holds.
+    fn product(&self, other: &Self, keep: &dyn Fn(usize, usize) -> bool) -> Self {
+        let mut data = [T::ZERO; 8];
+        for i in 0..8 {
+            for j in 0..8 {
+                let (a, b) = (BLADES[i], BLADES[j]);
+                if !keep(a, b) {
+                    continue;
+                }
+                let k = blade_index(a ^ b);
+                let signs = [
+                    reorder_sign(a, b),
+                    ORIENTATION[i],
+                    ORIENTATION[j],
+                    ORIENTATION[k],
+                ];
+                let positive = signs.iter().filter(|&&s| !s).count() % 2 == 0;
+                let term = self.data[i] * other.data[j];
+                data[k] = if positive {
+                    data[k] + term
+                } else {
+                    data[k] - term
+                };
+            }
+        }
+        return Self { data };
+    }
+
+    // Outer product, which adds grades.
+    pub fn wedge(&self, other: &Self) -> Self {
+        self.product(other, &|a, b| a & b == 0)
+    }
+
+    // Left contraction, which lowers the grade of other by the grade of self.
+    // For vectors this is the dot product.
+    pub fn inner(&self, other: &Self) -> Self {
+        self.product(other, &|a, b| a & b == a)
+    }
+
+    // Reverses the order of basis vectors in every component, negating grades 2 and 3.
+    pub fn reverse(&self) -> Self {
+        let mut data = self.data;
+        for (k, value) in data.iter_mut().enumerate() {
+            if GRADES[k] >= 2 {
+                *value = -*value;
+            }
+        }
+        return Self { data };
+    }
+
+    // Multiplication by the inverse pseudoscalar -I, mapping vectors to their normal
+    // planes and bivectors to their normals.
+    pub fn dual(&self) -> Self {
+        *self * -Self::I
+    }
+
+    // The cross product of the vector parts, as the dual of their wedge product.
+    pub fn cross(&self, other: &Self) -> Self {
+        self.grade_project(1).wedge(&other.grade_project(1)).dual()
+    }
+
+    // Scalar part of self * reverse(self), the squared length for vectors.
+    pub fn norm_sqr(&self) -> T {
+        (*self * self.reverse()).data[0]
+    }
+
+    // Rotate (and scale by norm_sqr(rotor)) as rotor * self * reverse(rotor).
+    pub fn rotate(&self, rotor: &Self) -> Self {
+        *rotor * *self * rotor.reverse()
+    }
+}
+
+macro_rules! impl_multivec3d_float {
+    ($($t:ty),*) => {$(
+        impl Multivec3D<$t> {
+            // Rotor for a rotation by angle in the given plane, counterclockwise when looking
+            // down on the plane from its normal (the dual of the plane).
+            pub fn rotor(plane: Self, angle: $t) -> Self {
+                let plane = plane.grade_project(2);
+                let unit = plane / plane.norm_sqr().sqrt();
+                Self::from((angle / 2.0).cos()) - unit * (angle / 2.0).sin()
+            }
+
+            // Rotor for a rotation by angle around axis, counterclockwise when looking
+            // down along the axis towards the origin.
+            pub fn rotor_around(axis: Self, angle: $t) -> Self {
+                Self::rotor(axis.grade_project(1).dual() * -1.0, angle)
+            }
+        }
+    )*};
+}
+
+impl_multivec3d_float!(f32, f64);
+
+impl<T> From<T> for Multivec3D<T>
+where
+    T: Num,
+{
+    fn from(value: T) -> Self {
+        let mut result = Self::ZERO;
+        result.data[0] = value;
+        return result;
+    }
+}
+
+impl<T> From<[T; 3]> for Multivec3D<T>
+where
+    T: Num,
+{
+    fn from(arr: [T; 3]) -> Self {
+        Self::vector(arr[0], arr[1], arr[2])
+    }
+}
+
+impl<T> From<(T, T, T)> for Multivec3D<T>
+where
+    T: Num,
+{
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::vector(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for Multivec3D<T>
+where
+    T: Num,
+{
+    fn from(p: Point3<T>) -> Self {
+        Self::vector(p.x, p.y, p.z)
+    }
+}
+
+impl<T> Display for Multivec3D<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let d = &self.data;
+        write!(
+            f,
+            "{} + {} * e1 + {} * e2 + {} * e3 + {} * e2 ∧ e3 + {} * e3 ∧ e1 + {} * e1 ∧ e2 + {} * e1 ∧ e2 ∧ e3",
+            d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7]
+        )
+    }
+}
+
+impl<T> Add for Multivec3D<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut data = self.data;
+        for (a, b) in zip(&mut data, other.data) {
+            *a = *a + b;
+        }
+        return Self { data };
+    }
+}
+
+impl<T> Add<T> for Multivec3D<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn add(self, value: T) -> Self {
+        self + Self::from(value)
+    }
+}
+
+impl<T> Sub for Multivec3D<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let mut data = self.data;
+        for (a, b) in zip(&mut data, other.data) {
+            *a = *a - b;
+        }
+        return Self { data };
+    }
+}
+
+impl<T> Sub<T> for Multivec3D<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn sub(self, value: T) -> Self {
+        self - Self::from(value)
+    }
+}
+
+// The geometric product.
+impl<T> Mul for Multivec3D<T>
+where
+    T: Num + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.product(&other, &|_, _| true)
+    }
+}
+
+impl<T> Mul<T> for Multivec3D<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            data: self.data.map(|x| x * rhs),
+        }
+    }
+}
+
+impl<T> Div<T> for Multivec3D<T>
+where
+    T: Num,
+{
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            data: self.data.map(|x| x / rhs),
+        }
+    }
+}
+
+impl<T> Neg for Multivec3D<T>
+where
+    T: Num + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            data: self.data.map(|x| -x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M = Multivec3D<i64>;
+
+    #[test]
+    fn basis_products() {
+        let (e1, e2, e3, i) = (M::E1, M::E2, M::E3, M::I);
+
+        assert_eq!(e1 * e1, M::from(1));
+        assert_eq!(e1 * e2, M::bivector(0, 0, 1));
+        assert_eq!(e2 * e1, M::bivector(0, 0, -1));
+        assert_eq!(e3 * e1, M::bivector(0, 1, 0));
+        assert_eq!(e1 * e2 * e3, i);
+        assert_eq!(i * i, M::from(-1));
+        // The pseudoscalar commutes with everything in 3D.
+        assert_eq!(i * e2, e2 * i);
+    }
+
+    #[test]
+    fn products() {
+        let a = M::vector(1, 2, 3);
+        let b = M::vector(-2, 0, 5);
+
+        assert_eq!(a.inner(&b), M::from(13));
+        assert_eq!(a.wedge(&b), M::bivector(10, -11, 4));
+        // For vectors, the geometric product is the sum of both.
+        assert_eq!(a * b, a.inner(&b) + a.wedge(&b));
+        assert_eq!(a.wedge(&a), M::ZERO);
+        assert_eq!(a.wedge(&b).wedge(&M::E1), M::I * 10);
+
+        let c = a + M::bivector(1, 1, 0) + M::I * 2;
+        assert_eq!(c.grade(), None);
+        assert_eq!(c.grade_project(2), M::bivector(1, 1, 0));
+        assert_eq!(c.grade_project(3).grade(), Some(3));
+        assert_eq!(c.reverse(), a - M::bivector(1, 1, 0) - M::I * 2);
+        assert_eq!(a.norm_sqr(), 14);
+    }
+
+    #[test]
+    fn duality() {
+        assert_eq!(M::E3.dual(), M::bivector(0, 0, -1));
+        assert_eq!(M::bivector(1, 2, 3).dual(), M::vector(1, 2, 3));
+        assert_eq!(M::E1.cross(&M::E2), M::E3);
+        assert_eq!(
+            M::vector(1, 2, 3).cross(&M::vector(4, 5, 6)),
+            M::vector(-3, 6, -3)
+        );
+    }
+
+    #[test]
+    fn hailstone_cross_products() {
+        // Example from year 2023 day 24: a rock thrown from p with velocity v hits every
+        // hailstone (p_k, v_k), hence (p - p_k) x (v - v_k) = 0, or equivalently the
+        // linear equations p_0 x v_0 - p_k x v_k = (v_k - v_0) x p + (p_0 - p_k) x v.
+        let hail: Vec<(M, M)> = [
+            ((19, 13, 30), (-2, 1, -2)),
+            ((18, 19, 22), (-1, -1, -2)),
+            ((20, 25, 34), (-2, -2, -4)),
+        ]
+        .into_iter()
+        .map(|(p, v)| (M::from(p), M::from(v)))
+        .collect();
+        let (p, v) = (M::vector(24, 13, 10), M::vector(-3, 1, 2));
+
+        for &(pk, vk) in &hail {
+            assert_eq!((p - pk).wedge(&(v - vk)), M::ZERO);
+        }
+        let (p0, v0) = hail[0];
+        for &(pk, vk) in &hail[1..] {
+            let b = p0.cross(&v0) - pk.cross(&vk);
+            assert_eq!(b, (vk - v0).cross(&p) + (p0 - pk).cross(&v));
+        }
+        assert_eq!(p.vector_part().iter().sum::<i64>(), 47);
+    }
+
+    #[test]
+    fn rotors() {
+        use std::f64::consts::PI;
+        type F = Multivec3D<f64>;
+        let close = |a: F, b: F| (a - b).norm_sqr() < 1e-20;
+
+        // A quarter turn in the e1 e2 plane maps e1 to e2.
+        let rotor = F::rotor(F::E1.wedge(&F::E2), PI / 2.0);
+        assert!(close(F::E1.rotate(&rotor), F::E2));
+        assert!(close(F::E3.rotate(&rotor), F::E3));
+        assert!((rotor.norm_sqr() - 1.0).abs() < 1e-12);
+
+        // A third of a turn around (1, 1, 1) cycles the axes.
+        let rotor = F::rotor_around(F::vector(1.0, 1.0, 1.0), 2.0 * PI / 3.0);
+        assert!(close(F::E1.rotate(&rotor), F::E2));
+        assert!(close(F::E2.rotate(&rotor), F::E3));
+
+        // Bivectors rotate along.
+        let plane = F::E1.wedge(&F::E3);
+        assert!(close(plane.rotate(&rotor), F::E2.wedge(&F::E1)));
+    }
+}
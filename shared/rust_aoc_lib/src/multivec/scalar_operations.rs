@@ -1,52 +1,56 @@
-use super::Multivec2D;
-use std::ops::{Add, Sub, Mul};
-
-// These are unfortunately necessary for left addition and multiplication with a scalar
-
-impl Add<Multivec2D<f64>> for f64 {
-    type Output = Multivec2D<f64>;
-
-    fn add(self, rhs: Multivec2D<f64>) -> Self::Output {
-        rhs + self
-    }
+use super::{Multivec2D, Multivec3D};
+use std::ops::{Add, Mul, Sub};
+
+// These are unfortunately necessary for left operations with a scalar, since
+// Rust cannot implement Add<Multivec2D<T>> for a generic T.
+
+macro_rules! impl_left_scalar_ops {
+    ($mv:ident; $($t:ty),*) => {$(
+        impl Add<$mv<$t>> for $t {
+            type Output = $mv<$t>;
+
+            fn add(self, rhs: $mv<$t>) -> Self::Output {
+                rhs + self
+            }
+        }
+
+        impl Sub<$mv<$t>> for $t {
+            type Output = $mv<$t>;
+
+            fn sub(self, rhs: $mv<$t>) -> Self::Output {
+                $mv::from(self) - rhs
+            }
+        }
+
+        impl Mul<$mv<$t>> for $t {
+            type Output = $mv<$t>;
+
+            fn mul(self, rhs: $mv<$t>) -> Self::Output {
+                rhs * self
+            }
+        }
+    )*};
 }
 
-impl Add<Multivec2D<i64>> for i64 {
-    type Output = Multivec2D<i64>;
-
-    fn add(self, rhs: Multivec2D<i64>) -> Self::Output {
-        rhs + self
-    }
-}
-
-impl Sub<Multivec2D<f64>> for f64 {
-    type Output = Multivec2D<f64>;
-
-    fn sub(self, rhs: Multivec2D<f64>) -> Self::Output {
-        -rhs + self
-    }
-}
-
-impl Sub<Multivec2D<i64>> for i64 {
-    type Output = Multivec2D<i64>;
-
-    fn sub(self, rhs: Multivec2D<i64>) -> Self::Output {
-        -rhs + self
-    }
-}
-
-impl Mul<Multivec2D<f64>> for f64 {
-    type Output = Multivec2D<f64>;
-
-    fn mul(self, rhs: Multivec2D<f64>) -> Self::Output {
-        rhs * self
-    }
-}
-
-impl Mul<Multivec2D<i64>> for i64 {
-    type Output = Multivec2D<i64>;
-
-    fn mul(self, rhs: Multivec2D<i64>) -> Self::Output {
-        rhs * self
+impl_left_scalar_ops!(Multivec2D; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+impl_left_scalar_ops!(Multivec3D; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn left_scalars() {
+        let e1 = Multivec2D::<u16>::E1;
+        assert_eq!(3 * e1 + 1, Multivec2D { data: [1, 3, 0, 0] });
+        assert_eq!(2 + e1, Multivec2D { data: [2, 1, 0, 0] });
+
+        let e2 = Multivec2D::<f32>::E2;
+        assert_eq!(
+            1.5 - e2,
+            Multivec2D {
+                data: [1.5, 0.0, -1.0, 0.0]
+            }
+        );
     }
 }
@@ -0,0 +1,133 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+// Numeric types with identity elements. Additions that may overflow should use
+// checked_add or saturating_add rather than +; graph::Cost builds infinite path
+// costs on top of checked_add.
+pub trait Num:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    // None if the sum is not representable.
+    fn checked_add(self, other: Self) -> Option<Self>;
+    // Clamps the sum to the representable range.
+    fn saturating_add(self, other: Self) -> Self;
+    // Nearest f64, which may lose precision for large integers.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_num_int {
+    ($($t:ty),*) => {$(
+        impl Num for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_num_float {
+    ($($t:ty),*) => {$(
+        impl Num for $t {
+            const ZERO: $t = 0.0;
+            const ONE: $t = 1.0;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                let sum = self + other;
+                if sum.is_nan() { None } else { Some(sum) }
+            }
+
+            fn saturating_add(self, other: Self) -> Self {
+                // Floats saturate to infinity by themselves.
+                self + other
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+// Primitive integers, signed or unsigned, for number theory such as utils::gcd.
+pub trait Integer: Num + Ord + Rem<Output = Self> {
+    // None if the product is not representable.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    // None for the minimum of a signed type.
+    fn checked_abs(self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($abs:expr; $($t:ty),*) => {$(
+        impl Integer for $t {
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_abs(self) -> Option<Self> {
+                $abs(self)
+            }
+        }
+    )*};
+}
+
+impl_num_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_integer!(|x: Self| x.checked_abs(); i8, i16, i32, i64, i128, isize);
+impl_integer!(Some; u8, u16, u32, u64, u128, usize);
+impl_num_float!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum<T: Num>(values: &[T]) -> Option<T> {
+        values
+            .iter()
+            .try_fold(T::ZERO, |acc, &value| acc.checked_add(value))
+    }
+
+    #[test]
+    fn identities() {
+        assert_eq!((u8::ZERO, u8::ONE), (0, 1));
+        assert_eq!((f32::ZERO, f32::ONE), (0.0, 1.0));
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(Integer::checked_abs(-5_i8), Some(5));
+        assert_eq!(Integer::checked_abs(i8::MIN), None);
+        assert_eq!(Integer::checked_abs(u8::MAX), Some(u8::MAX));
+        assert_eq!(Integer::checked_mul(16_u8, 16), None);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(Num::checked_add(u32::MAX, 1), None);
+        assert_eq!(Num::saturating_add(u32::MAX, 1), u32::MAX);
+        assert_eq!(Num::saturating_add(i8::MAX, i8::MAX), i8::MAX);
+        assert_eq!(Num::saturating_add(f64::INFINITY, 1.0), f64::INFINITY);
+        assert_eq!(Num::checked_add(f64::INFINITY, -f64::INFINITY), None);
+
+        assert_eq!(sum(&[100_u8, 100, 50]), Some(250));
+        assert_eq!(sum(&[100_u8, 100, 100]), None);
+    }
+}
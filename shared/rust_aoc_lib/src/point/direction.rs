@@ -0,0 +1,199 @@
+use super::Point2;
+use crate::complex::Complex;
+use std::str::FromStr;
+
+// Compass directions on a puzzle map, where north is up, i.e. towards decreasing y (or row index).
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+use Direction::*;
+
+impl Direction {
+    // Clockwise, starting from north.
+    pub const ORTHOGONAL: [Direction; 4] = [N, E, S, W];
+    pub const ALL: [Direction; 8] = [N, NE, E, SE, S, SW, W, NW];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&d| d == self).unwrap()
+    }
+
+    // Rotate clockwise by the given number of eighth turns. Negative turns rotate counterclockwise.
+    pub fn rotate(self, eighth_turns: i32) -> Self {
+        Self::ALL[(self.index() as i32 + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_orthogonal(self) -> bool {
+        self.index().is_multiple_of(2)
+    }
+
+    // Unit step (dx, dy).
+    pub fn delta(self) -> Point2<i64> {
+        let (x, y) = match self {
+            N => (0, -1),
+            NE => (1, -1),
+            E => (1, 0),
+            SE => (1, 1),
+            S => (0, 1),
+            SW => (-1, 1),
+            W => (-1, 0),
+            NW => (-1, -1),
+        };
+        Point2 { x, y }
+    }
+
+    // Unit step (di, dj) in grid row and column, as used by the grid module.
+    pub fn grid_step(self) -> (i64, i64) {
+        let delta = self.delta();
+        (delta.y, delta.x)
+    }
+
+    // Inverse of grid_step, for unit steps only.
+    pub fn from_grid_step((di, dj): (i64, i64)) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.grid_step() == (di, dj))
+    }
+}
+
+// Single character directions: arrows ^>v<, UDLR and NESW.
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Ok(N),
+            '>' | 'R' | 'E' => Ok(E),
+            'V' | 'D' | 'S' => Ok(S),
+            '<' | 'L' | 'W' => Ok(W),
+            _ => Err(format!("Not a direction: {c:?}.")),
+        }
+    }
+}
+
+// As TryFrom<char>, but also accepting the diagonals NE, SE, SW and NW.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, _) => Direction::try_from(c),
+            (Some(_), Some(_), None) => match s.to_ascii_uppercase().as_str() {
+                "NE" => Ok(NE),
+                "SE" => Ok(SE),
+                "SW" => Ok(SW),
+                "NW" => Ok(NW),
+                _ => Err(format!("Not a direction: {s:?}.")),
+            },
+            _ => Err(format!("Not a direction: {s:?}.")),
+        }
+    }
+}
+
+// Unit steps as points and as x + I*y for every signed integer type.
+// Since y points down, multiplying by I turns right.
+macro_rules! impl_direction_conversions {
+    ($($t:ty),*) => {$(
+        impl From<Direction> for Point2<$t> {
+            fn from(dir: Direction) -> Self {
+                let delta = dir.delta();
+                Point2 {
+                    x: delta.x as $t,
+                    y: delta.y as $t,
+                }
+            }
+        }
+
+        impl From<Direction> for Complex<$t> {
+            fn from(dir: Direction) -> Self {
+                Point2::<$t>::from(dir).into()
+            }
+        }
+
+        impl TryFrom<Complex<$t>> for Direction {
+            type Error = String;
+
+            fn try_from(z: Complex<$t>) -> Result<Self, Self::Error> {
+                Direction::ALL
+                    .into_iter()
+                    .find(|&dir| Complex::<$t>::from(dir) == z)
+                    .ok_or(format!("Not a unit step: {z:?}."))
+            }
+        }
+    )*};
+}
+
+impl_direction_conversions!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(N.turn_right(), E);
+        assert_eq!(N.turn_left(), W);
+        assert_eq!(SE.reverse(), NW);
+        assert_eq!(NW.rotate(1), N);
+        assert_eq!(N.rotate(-9), NW);
+        assert!(Direction::ORTHOGONAL.iter().all(|d| d.is_orthogonal()));
+        assert_eq!(
+            Direction::ALL.iter().filter(|d| d.is_orthogonal()).count(),
+            4
+        );
+
+        for dir in Direction::ALL {
+            assert_eq!(dir.reverse().delta(), -dir.delta());
+            assert_eq!(Direction::from_grid_step(dir.grid_step()), Some(dir));
+            // Turning right agrees with multiplication by I.
+            let i = Complex { real: 0, imag: 1 };
+            let z: Complex<i32> = dir.into();
+            assert_eq!(Complex::from(dir.turn_right()), z * i);
+            assert_eq!(Direction::try_from(z), Ok(dir));
+        }
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(N.delta(), Point2::new(0, -1));
+        assert_eq!(N.grid_step(), (-1, 0));
+        assert_eq!(SW.grid_step(), (1, -1));
+        assert_eq!(Direction::from_grid_step((2, 0)), None);
+        assert!(Direction::try_from(Complex {
+            real: 2i64,
+            imag: 0
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn parsing() {
+        let arrows: Vec<Direction> = "^>v<".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(arrows, Direction::ORTHOGONAL);
+        let letters: Vec<Direction> = "URDL".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(letters, Direction::ORTHOGONAL);
+        assert_eq!("W".parse(), Ok(W));
+        assert_eq!("ne".parse(), Ok(NE));
+        assert!("X".parse::<Direction>().is_err());
+        assert!("NNE".parse::<Direction>().is_err());
+    }
+}
@@ -0,0 +1,5 @@
+mod direction;
+mod point;
+
+pub use direction::*;
+pub use point::*;
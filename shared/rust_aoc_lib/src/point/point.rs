@@ -0,0 +1,269 @@
+use crate::complex::Complex;
+use crate::num::Num;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+// Points in the plane. For puzzle maps x is the column and y the row, such that y grows downwards.
+#[derive(Debug, PartialEq, Eq, Hash, Default, Copy, Clone)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Default, Copy, Clone)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+fn abs_diff<T: Num>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Num> Point2<T> {
+    pub const ORIGIN: Self = Self {
+        x: T::ZERO,
+        y: T::ZERO,
+    };
+
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        f64::hypot(dx.to_f64(), dy.to_f64())
+    }
+}
+
+impl<T: Num> Point3<T> {
+    pub const ORIGIN: Self = Self {
+        x: T::ZERO,
+        y: T::ZERO,
+        z: T::ZERO,
+    };
+
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        [abs_diff(self.y, other.y), abs_diff(self.z, other.z)]
+            .into_iter()
+            .fold(
+                abs_diff(self.x, other.x),
+                |max, d| if d > max { d } else { max },
+            )
+    }
+
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        let (dx, dy, dz) = (
+            abs_diff(self.x, other.x).to_f64(),
+            abs_diff(self.y, other.y).to_f64(),
+            abs_diff(self.z, other.z).to_f64(),
+        );
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    // Cross product of the points as vectors.
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+// Component-wise arithmetic, shared by both point types.
+macro_rules! impl_point_ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $(self.$field += other.$field;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$field -= other.$field;)*
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Div<Output = T> + Copy> Div<T> for $point<T> {
+            type Output = Self;
+
+            fn div(self, rhs: T) -> Self {
+                Self { $($field: self.$field / rhs),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),* }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2 { x, y });
+impl_point_ops!(Point3 { x, y, z });
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+// x is the real part and y the imaginary part.
+impl<T> From<Complex<T>> for Point2<T> {
+    fn from(z: Complex<T>) -> Self {
+        Self {
+            x: z.real,
+            y: z.imag,
+        }
+    }
+}
+
+impl<T> From<Point2<T>> for Complex<T> {
+    fn from(point: Point2<T>) -> Self {
+        Complex {
+            real: point.x,
+            imag: point.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point2::new(3, -2);
+        let q = Point2::new(-1, 5);
+        assert_eq!(p + q, Point2::new(2, 3));
+        assert_eq!(p - q, Point2::new(4, -7));
+        assert_eq!(-p * 2, Point2::new(-6, 4));
+        assert_eq!(q / 2, Point2::new(0, 2));
+        p += q;
+        p -= Point2::new(2, 0);
+        assert_eq!(p, Point2::new(0, 3));
+        assert_eq!(p.to_string(), "(0, 3)");
+
+        let a = Point3::new(1, 0, 0);
+        let b = Point3::new(0, 1, 0);
+        assert_eq!(a.cross(&b), Point3::new(0, 0, 1));
+        assert_eq!(a.dot(&b), 0);
+        assert_eq!(a + b - Point3::ORIGIN, Point3::from((1, 1, 0)));
+    }
+
+    #[test]
+    fn distances() {
+        let p = Point2::new(1u32, 7);
+        let q = Point2::new(4u32, 3);
+        assert_eq!(p.manhattan(&q), 7);
+        assert_eq!(p.chebyshev(&q), 4);
+        assert_eq!(p.euclidean(&q), 5.0);
+
+        let a = Point3::new(-1i64, 2, 3);
+        let b = Point3::new(1, -4, 3);
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!(
+            Point3::new(0.0, 3.0, 0.0).euclidean(&Point3::new(4.0, 0.0, 0.0)),
+            5.0
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let p: Point2<i64> = (2, -3).into();
+        let z: Complex<i64> = p.into();
+        assert_eq!((z.real, z.imag), (2, -3));
+        assert_eq!(Point2::from(z), p);
+        assert_eq!(<(i64, i64)>::from(p), (2, -3));
+    }
+}
@@ -0,0 +1,315 @@
+// Primes and factorisation of u64: a segmented sieve for ranges of primes, a
+// deterministic Miller-Rabin test, and Pollard's rho for factoring what trial
+// division leaves over.
+
+use crate::modular::pow_mod;
+use crate::utils::gcd;
+use std::ops::Range;
+
+const SEGMENT: u64 = 1 << 16;
+const SMALL_PRIMES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+// All primes in the range in increasing order, by a segmented sieve of Eratosthenes.
+// The range is sieved one segment at a time by the base primes up to sqrt(range.end),
+// so memory is the base primes and the result. Finding the base primes takes time
+// growing with that square root, so ranges much narrower than it are tested number
+// by number instead.
+pub fn primes_in(range: Range<u64>) -> Vec<u64> {
+    let (low, high) = (range.start.max(2), range.end);
+    if low >= high {
+        return vec![];
+    }
+    let limit = (high - 1).isqrt();
+    if high - low < limit / 64 {
+        return (low..high).filter(|&n| is_prime(n)).collect();
+    }
+    let mut primes = vec![];
+    sieve(low..high, &base_primes(limit), &mut |p| primes.push(p));
+    return primes;
+}
+
+// The primes up to limit < 2^32, stored narrowly as they make up most of the memory.
+fn base_primes(limit: u64) -> Vec<u32> {
+    if limit < 2 {
+        return vec![];
+    }
+    let mut primes = vec![];
+    sieve(2..limit + 1, &base_primes(limit.isqrt()), &mut |p| {
+        primes.push(p as u32)
+    });
+    return primes;
+}
+
+// Report the primes in the range, given all primes up to sqrt(range.end) and low >= 2.
+fn sieve(range: Range<u64>, base: &[u32], found: &mut dyn FnMut(u64)) {
+    let mut composite = [0_u64; (SEGMENT / 64) as usize];
+    let mut start = range.start;
+    while start < range.end {
+        let end = start.saturating_add(SEGMENT).min(range.end);
+        composite.fill(0);
+        for p in base.iter().map(|&p| p as u64).take_while(|&p| p * p < end) {
+            // The first multiple in the segment, if there is any below u64::MAX.
+            let Some(first) = start.div_ceil(p).checked_mul(p) else {
+                continue;
+            };
+            for m in (first.max(p * p)..end).step_by(p as usize) {
+                let k = m - start;
+                composite[(k / 64) as usize] |= 1 << (k % 64);
+            }
+        }
+        for n in start..end {
+            if composite[((n - start) / 64) as usize] & (1 << ((n - start) % 64)) == 0 {
+                found(n);
+            }
+        }
+        start = end;
+    }
+}
+
+pub fn primes_below(n: u64) -> Vec<u64> {
+    primes_in(0..n)
+}
+
+// Deterministic Miller-Rabin: the first 12 primes as bases suffice for all u64.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = SMALL_PRIMES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    'bases: for a in SMALL_PRIMES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul(x, x);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    return true;
+}
+
+// A non-trivial divisor of the odd composite n, by Pollard's rho with Brent's
+// cycle detection and batched gcds.
+fn pollard_rho(n: u64) -> u64 {
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    for c in 1.. {
+        let f = |x: u64| ((x as u128 * x as u128 + c) % n as u128) as u64;
+        let (mut x, mut y, mut saved) = (2, 2, 2);
+        let (mut g, mut q, mut r) = (1, 1, 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                saved = y;
+                for _ in 0..128.min(r - k) {
+                    y = f(y);
+                    q = mul(q, x.abs_diff(y));
+                }
+                g = gcd(q, n);
+                k += 128;
+            }
+            r *= 2;
+        }
+        // The batch overshot, so redo its steps one gcd at a time.
+        if g == n {
+            g = loop {
+                saved = f(saved);
+                let g = gcd(x.abs_diff(saved), n);
+                if g > 1 {
+                    break g;
+                }
+            };
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!();
+}
+
+// Prime factors with their multiplicities, in increasing order. Panics for 0.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "Zero has no factorization.");
+    let mut factors = vec![];
+    let mut rest = n;
+    for p in SMALL_PRIMES {
+        while rest.is_multiple_of(p) {
+            factors.push(p);
+            rest /= p;
+        }
+    }
+    let mut stack = vec![rest];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            factors.push(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.extend([d, m / d]);
+        }
+    }
+    factors.sort();
+
+    let mut powers: Vec<(u64, u32)> = vec![];
+    for p in factors {
+        match powers.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => powers.push((p, 1)),
+        }
+    }
+    return powers;
+}
+
+// All positive divisors in increasing order. Panics for 0.
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut divisors = vec![1];
+    for (p, e) in factorize(n) {
+        let smaller = divisors.clone();
+        let mut power = 1;
+        for _ in 0..e {
+            power *= p;
+            divisors.extend(smaller.iter().map(|d| d * power));
+        }
+    }
+    divisors.sort();
+    return divisors;
+}
+
+// Number of positive divisors. Panics for 0.
+pub fn divisor_count(n: u64) -> u64 {
+    factorize(n).iter().map(|&(_, e)| e as u64 + 1).product()
+}
+
+// Sum of all positive divisors, which may exceed u64. Panics for 0.
+pub fn divisor_sum(n: u64) -> u128 {
+    factorize(n)
+        .iter()
+        .map(|&(p, e)| (0..=e).map(|k| (p as u128).pow(k)).sum::<u128>())
+        .product()
+}
+
+// Euler's totient: the count of 1 <= k <= n coprime to n. Panics for 0.
+pub fn totient(n: u64) -> u64 {
+    factorize(n)
+        .iter()
+        .map(|&(p, e)| p.pow(e - 1) * (p - 1))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sieving() {
+        assert_eq!(primes_below(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_below(2), vec![]);
+        assert_eq!(primes_below(1_000_000).len(), 78498);
+        assert_eq!(primes_in(100..200).len(), 21);
+        assert_eq!(primes_in(500_000..1_000_000).len(), 78498 - 41538);
+
+        // Far from 0, across segment boundaries.
+        let start = 1_000_000_000_000;
+        let primes = primes_in(start..start + 3 * SEGMENT);
+        let expected: Vec<u64> = (start..start + 3 * SEGMENT)
+            .filter(|&n| is_prime(n))
+            .collect();
+        assert_eq!(primes, expected);
+        assert_eq!(primes[0], 1_000_000_000_039);
+
+        // Near the top, where the multiples of large base primes would overflow.
+        let top = primes_in(u64::MAX - 100..u64::MAX);
+        assert_eq!(top.last(), Some(&18446744073709551557));
+        assert!(primes_in(u64::MAX - 5..u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn primality() {
+        let small: Vec<u64> = (0..100).filter(|&n| is_prime(n)).collect();
+        assert_eq!(small, primes_below(100));
+        for p in [1_000_000_007, (1 << 61) - 1, 18446744073709551557] {
+            assert!(is_prime(p));
+        }
+        // A Carmichael number and strong pseudoprimes to several small bases.
+        for n in [
+            561,
+            3215031751,
+            3825123056546413051,
+            1_000_000_007 * 998_244_353,
+        ] {
+            assert!(!is_prime(n));
+        }
+    }
+
+    #[test]
+    fn factorization() {
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(
+            factorize(600851475143),
+            vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]
+        );
+        assert_eq!(
+            factorize(u64::MAX),
+            vec![
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6700417, 1)
+            ]
+        );
+        assert_eq!(
+            factorize(1_000_000_007 * 998_244_353),
+            vec![(998_244_353, 1), (1_000_000_007, 1)]
+        );
+        assert_eq!(
+            factorize(4_294_967_291 * 4_294_967_291),
+            vec![(4_294_967_291, 2)]
+        );
+        assert_eq!(factorize(1 << 63), vec![(2, 63)]);
+    }
+
+    #[test]
+    fn divisor_functions() {
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(28), vec![1, 2, 4, 7, 14, 28]);
+        assert_eq!(divisor_sum(28), 56);
+        assert_eq!(divisor_count(720720), 240);
+        assert_eq!(divisors(720720).len(), 240);
+        assert!(divisor_sum(u64::MAX) > u64::MAX as u128);
+
+        // Example from year 2015 day 20: house n gets 10 presents per divisor's elf.
+        let presents: Vec<u128> = (1..=9).map(|n| 10 * divisor_sum(n)).collect();
+        assert_eq!(presents, vec![10, 30, 40, 70, 60, 120, 80, 150, 130]);
+    }
+
+    #[test]
+    fn totients() {
+        assert_eq!(totient(1), 1);
+        assert_eq!(totient(36), 12);
+        assert_eq!(totient(1_000_000_007), 1_000_000_006);
+        // Gauss: the totients of the divisors of n sum to n.
+        for n in [1, 12, 97, 720720, 600851475143] {
+            assert_eq!(divisors(n).into_iter().map(totient).sum::<u64>(), n);
+        }
+        let coprime = (1..=36).filter(|&k| gcd(k, 36) == 1).count() as u64;
+        assert_eq!(coprime, totient(36));
+    }
+}
@@ -0,0 +1,3 @@
+mod rational;
+
+pub use rational::*;
@@ -0,0 +1,635 @@
+use crate::num::Num;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// Exact fraction numer / denom, always in lowest terms with denom > 0, so equal
+// values compare and hash equal. Operators panic on overflow, the checked_
+// methods return None instead.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Rational<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Copy> Rational<T> {
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+}
+
+// Unsigned 256-bit integers as (high, low) halves, only for approximating sums that
+// overflow. Tuples compare in the right order.
+type Wide = (u128, u128);
+
+fn wide_mul(a: u128, b: u128) -> Wide {
+    let mask = u64::MAX as u128;
+    let (a1, a0, b1, b0) = (a >> 64, a & mask, b >> 64, b & mask);
+    let (low, cross1, cross2, high) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (low >> 64) + (cross1 & mask) + (cross2 & mask);
+    (
+        high + (cross1 >> 64) + (cross2 >> 64) + (mid >> 64),
+        (mid << 64) | (low & mask),
+    )
+}
+
+fn wide_add(a: Wide, b: Wide) -> Wide {
+    let (low, carry) = a.1.overflowing_add(b.1);
+    (a.0 + b.0 + carry as u128, low)
+}
+
+fn wide_sub(a: Wide, b: Wide) -> Wide {
+    let (low, borrow) = a.1.overflowing_sub(b.1);
+    (a.0 - b.0 - borrow as u128, low)
+}
+
+// Long division, one bit at a time. Assumes d < 2^255 so the remainder never overflows.
+fn wide_div_rem(n: Wide, d: Wide) -> (Wide, Wide) {
+    let (mut quotient, mut rem) = ((0, 0), (0, 0));
+    for i in (0..256).rev() {
+        let bit = if i >= 128 { n.0 >> (i - 128) } else { n.1 >> i } & 1;
+        rem = ((rem.0 << 1) | (rem.1 >> 127), (rem.1 << 1) | bit);
+        if rem >= d {
+            rem = wide_sub(rem, d);
+            if i >= 128 {
+                quotient.0 |= 1 << (i - 128);
+            } else {
+                quotient.1 |= 1 << i;
+            }
+        }
+    }
+    (quotient, rem)
+}
+
+// The fraction p / q closest to n / d with q <= max_denom, from the continued fraction
+// expansion: the last convergent that fits, or the largest semiconvergent after it if
+// that is closer.
+fn best_approximation(mut n: Wide, mut d: Wide, max_denom: u128) -> (u128, u128) {
+    let (mut p0, mut q0, mut p1, mut q1) = (0u128, 1u128, 1u128, 0u128);
+    loop {
+        let (a, r) = wide_div_rem(n, d);
+        let next = (a.0 == 0)
+            .then(|| {
+                Some((
+                    p0.checked_add(a.1.checked_mul(p1)?)?,
+                    q0.checked_add(a.1.checked_mul(q1)?)?,
+                ))
+            })
+            .flatten()
+            .filter(|&(_, q)| q <= max_denom);
+        let Some((p, q)) = next else {
+            let k = (max_denom - q0) / q1;
+            if a.0 == 0 && 2 * k > a.1 {
+                return (p0 + k * p1, q0 + k * q1);
+            }
+            return (p1, q1);
+        };
+        (p0, q0, p1, q1) = (p1, q1, p, q);
+        if r == (0, 0) {
+            return (p1, q1);
+        }
+        (n, d) = (d, r);
+    }
+}
+
+macro_rules! impl_rational {
+    ($($t:ty),*) => {$(
+        impl Rational<$t> {
+            pub const ZERO: Self = Self::from_integer(0);
+            pub const ONE: Self = Self::from_integer(1);
+
+            // Panics if denom is zero.
+            pub fn new(numer: $t, denom: $t) -> Self {
+                assert!(denom != 0, "Rational with zero denominator.");
+                Self::checked_new(numer, denom).expect("Rational overflow.")
+            }
+
+            // None if denom is zero or the reduced fraction is not representable.
+            pub fn checked_new(numer: $t, denom: $t) -> Option<Self> {
+                if denom == 0 {
+                    return None;
+                }
+                // The gcd only wraps when it is 2^(bits - 1), where the divisions still work out.
+                let g = Self::gcd(numer, denom);
+                let (numer, denom) = (numer / g, denom / g);
+                if denom < 0 {
+                    return Some(Self {
+                        numer: numer.checked_neg()?,
+                        denom: denom.checked_neg()?,
+                    });
+                }
+                return Some(Self { numer, denom });
+            }
+
+            fn gcd(a: $t, b: $t) -> $t {
+                let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                return a as $t;
+            }
+
+            pub const fn from_integer(n: $t) -> Self {
+                Self { numer: n, denom: 1 }
+            }
+
+            pub fn is_integer(&self) -> bool {
+                self.denom == 1
+            }
+
+            pub fn to_integer(&self) -> Option<$t> {
+                self.is_integer().then_some(self.numer)
+            }
+
+            pub fn floor(&self) -> $t {
+                self.numer.div_euclid(self.denom)
+            }
+
+            pub fn ceil(&self) -> $t {
+                self.floor() + (self.numer.rem_euclid(self.denom) != 0) as $t
+            }
+
+            // Nearest integer, rounding halves away from zero.
+            pub fn round(&self) -> $t {
+                let (q, r) = (self.numer / self.denom, self.numer % self.denom);
+                if r.unsigned_abs() >= self.denom.unsigned_abs() - r.unsigned_abs() {
+                    return q + self.numer.signum();
+                }
+                return q;
+            }
+
+            pub fn trunc(&self) -> $t {
+                self.numer / self.denom
+            }
+
+            // self - floor(self), in [0, 1).
+            pub fn fract(&self) -> Self {
+                Self {
+                    numer: self.numer.rem_euclid(self.denom),
+                    denom: self.denom,
+                }
+            }
+
+            // Panics for numer == MIN.
+            pub fn abs(&self) -> Self {
+                self.checked_abs().expect("Rational overflow.")
+            }
+
+            pub fn checked_abs(&self) -> Option<Self> {
+                Some(Self {
+                    numer: self.numer.checked_abs()?,
+                    denom: self.denom,
+                })
+            }
+
+            pub fn signum(&self) -> $t {
+                self.numer.signum()
+            }
+
+            // Panics if self is zero.
+            pub fn recip(&self) -> Self {
+                Self::new(self.denom, self.numer)
+            }
+
+            // self^k, where negative k take powers of the reciprocal. Panics on 0^k for k < 0.
+            pub fn pow(&self, k: i32) -> Self {
+                let base = if k < 0 { self.recip() } else { *self };
+                let k = k.unsigned_abs();
+                // Powers of coprime numbers stay coprime.
+                Self {
+                    numer: base.numer.checked_pow(k).expect("Rational overflow."),
+                    denom: base.denom.checked_pow(k).expect("Rational overflow."),
+                }
+            }
+
+            pub fn checked_neg(self) -> Option<Self> {
+                Some(Self {
+                    numer: self.numer.checked_neg()?,
+                    denom: self.denom,
+                })
+            }
+
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                // Scale by the lcm of the denominators rather than their product.
+                let g = Self::gcd(self.denom, other.denom);
+                let numer = (self.numer.checked_mul(other.denom / g)?)
+                    .checked_add(other.numer.checked_mul(self.denom / g)?)?;
+                Self::checked_new(numer, (self.denom / g).checked_mul(other.denom)?)
+            }
+
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.checked_add(other.checked_neg()?)
+            }
+
+            pub fn checked_mul(self, other: Self) -> Option<Self> {
+                // Cancel crosswise first, which leaves the product in lowest terms.
+                let g1 = Self::gcd(self.numer, other.denom);
+                let g2 = Self::gcd(other.numer, self.denom);
+                Some(Self {
+                    numer: (self.numer / g1).checked_mul(other.numer / g2)?,
+                    denom: (self.denom / g2).checked_mul(other.denom / g1)?,
+                })
+            }
+
+            // None if other is zero or on overflow.
+            pub fn checked_div(self, other: Self) -> Option<Self> {
+                self.checked_mul(Self::checked_new(other.denom, other.numer)?)
+            }
+        }
+
+        impl Num for Rational<$t> {
+            const ZERO: Self = Self::from_integer(0);
+            const ONE: Self = Self::from_integer(1);
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                Rational::<$t>::checked_add(self, other)
+            }
+
+            // Clamps to +-MAX when the sum lies beyond it. A sum within range whose reduced
+            // form does not fit becomes the closest fraction with a small enough denominator.
+            fn saturating_add(self, other: Self) -> Self {
+                if let Some(sum) = self.checked_add(other) {
+                    return sum;
+                }
+                // The sum lies in [s, s + 2) for the sum s of the floors, and the
+                // fractional parts decide the cases next to the bounds.
+                let max = Self::from_integer(<$t>::MAX);
+                let (f1, f2) = (self.fract(), other.fract());
+                match self.floor().checked_add(other.floor()) {
+                    None if self.numer > 0 => max,
+                    None => -max,
+                    Some(s) if s == <$t>::MAX && (f1 != Self::ZERO || f2 != Self::ZERO) => max,
+                    Some(s) if s == <$t>::MAX - 1 && f1 > Self::ONE - f2 => max,
+                    Some(s) if s == <$t>::MIN && f1 < Self::ONE - f2 => -max,
+                    Some(s) => {
+                        // Sum the fractional parts exactly, then approximate with a
+                        // denominator small enough that adding s cannot overflow.
+                        let (n1, d1) = (f1.numer as u128, f1.denom as u128);
+                        let (n2, d2) = (f2.numer as u128, f2.denom as u128);
+                        let numer = wide_add(wide_mul(n1, d2), wide_mul(n2, d1));
+                        let max_denom = (<$t>::MAX as u128 / (s.unsigned_abs() as u128 + 2)).max(1);
+                        let (p, q) = best_approximation(numer, wide_mul(d1, d2), max_denom);
+                        Self::from_integer(s) + Self::new(p as $t, q as $t)
+                    }
+                }
+            }
+
+            fn to_f64(self) -> f64 {
+                self.numer as f64 / self.denom as f64
+            }
+        }
+
+        impl From<$t> for Rational<$t> {
+            fn from(n: $t) -> Self {
+                Self::from_integer(n)
+            }
+        }
+
+        impl Ord for Rational<$t> {
+            // Compares continued fraction expansions term by term, which never overflows.
+            fn cmp(&self, other: &Self) -> Ordering {
+                let (mut a, mut b) = (self.numer, self.denom);
+                let (mut c, mut d) = (other.numer, other.denom);
+                let mut flipped = false;
+                loop {
+                    let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+                    let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+                    let ordering = match (r1, r2) {
+                        _ if q1 != q2 => q1.cmp(&q2),
+                        (0, 0) => Ordering::Equal,
+                        (0, _) => Ordering::Less,
+                        (_, 0) => Ordering::Greater,
+                        // r1 / b < r2 / d if and only if b / r1 > d / r2.
+                        _ => {
+                            (a, b, c, d) = (b, r1, d, r2);
+                            flipped = !flipped;
+                            continue;
+                        }
+                    };
+                    return if flipped { ordering.reverse() } else { ordering };
+                }
+            }
+        }
+
+        impl PartialOrd for Rational<$t> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Display for Rational<$t> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if self.denom == 1 {
+                    write!(f, "{}", self.numer)
+                } else {
+                    write!(f, "{}/{}", self.numer, self.denom)
+                }
+            }
+        }
+
+        // Parses fractions "-3/4", integers "7" and decimals "1.25".
+        impl FromStr for Rational<$t> {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let error = || format!("Invalid rational number {s:?}.");
+                let parse = |part: &str| part.trim().parse::<$t>().map_err(|_| error());
+                let s = s.trim();
+                if let Some((numer, denom)) = s.split_once('/') {
+                    return Self::checked_new(parse(numer)?, parse(denom)?).ok_or_else(error);
+                }
+                if let Some((int, frac)) = s.split_once('.') {
+                    if frac.is_empty() || !frac.chars().all(|c| c.is_ascii_digit()) {
+                        return Err(error());
+                    }
+                    let denom = (10 as $t).checked_pow(frac.len() as u32).ok_or_else(error)?;
+                    return Self::checked_new(parse(&format!("{int}{frac}"))?, denom).ok_or_else(error);
+                }
+                Ok(Self::from_integer(parse(s)?))
+            }
+        }
+
+        impl Neg for Rational<$t> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                self.checked_neg().expect("Rational overflow.")
+            }
+        }
+
+        impl_rational_op!($t; Add, add, checked_add; AddAssign, add_assign);
+        impl_rational_op!($t; Sub, sub, checked_sub; SubAssign, sub_assign);
+        impl_rational_op!($t; Mul, mul, checked_mul; MulAssign, mul_assign);
+        impl_rational_op!($t; Div, div, checked_div; DivAssign, div_assign);
+
+        impl Sum for Rational<$t> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, x| acc + x)
+            }
+        }
+
+        impl<'a> Sum<&'a Rational<$t>> for Rational<$t> {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, x| acc + *x)
+            }
+        }
+
+        impl Product for Rational<$t> {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |acc, x| acc * x)
+            }
+        }
+
+        impl<'a> Product<&'a Rational<$t>> for Rational<$t> {
+            fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |acc, x| acc * *x)
+            }
+        }
+    )*};
+}
+
+// Binary operator between rationals, with an integer on either side, and its assigning form.
+// Division by zero panics like the overflows.
+macro_rules! impl_rational_op {
+    ($t:ty; $trait:ident, $method:ident, $checked:ident; $assign_trait:ident, $assign_method:ident) => {
+        impl $trait for Rational<$t> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                self.$checked(other)
+                    .expect("Rational overflow or division by zero.")
+            }
+        }
+
+        impl $trait<$t> for Rational<$t> {
+            type Output = Self;
+
+            fn $method(self, other: $t) -> Self {
+                self.$method(Self::from_integer(other))
+            }
+        }
+
+        impl $trait<Rational<$t>> for $t {
+            type Output = Rational<$t>;
+
+            fn $method(self, other: Rational<$t>) -> Rational<$t> {
+                Rational::<$t>::from_integer(self).$method(other)
+            }
+        }
+
+        impl $assign_trait for Rational<$t> {
+            fn $assign_method(&mut self, other: Self) {
+                *self = self.$method(other);
+            }
+        }
+
+        impl $assign_trait<$t> for Rational<$t> {
+            fn $assign_method(&mut self, other: $t) {
+                *self = self.$method(other);
+            }
+        }
+    };
+}
+
+impl_rational!(i64, i128);
+
+// Lossless conversions from narrower integers.
+macro_rules! impl_rational_from {
+    ($t:ty; $($from:ty),*) => {$(
+        impl From<$from> for Rational<$t> {
+            fn from(n: $from) -> Self {
+                Self::from_integer(n as $t)
+            }
+        }
+    )*};
+}
+
+impl_rational_from!(i64; i8, i16, i32, u8, u16, u32);
+impl_rational_from!(i128; i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl From<Rational<i64>> for Rational<i128> {
+    fn from(r: Rational<i64>) -> Self {
+        Self {
+            numer: r.numer as i128,
+            denom: r.denom as i128,
+        }
+    }
+}
+
+impl TryFrom<Rational<i128>> for Rational<i64> {
+    type Error = String;
+
+    fn try_from(r: Rational<i128>) -> Result<Self, Self::Error> {
+        match (i64::try_from(r.numer), i64::try_from(r.denom)) {
+            (Ok(numer), Ok(denom)) => Ok(Self { numer, denom }),
+            _ => Err(format!("Rational {r} does not fit in i64.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complex::Complex;
+    use crate::multivec::Multivec2D;
+
+    type Q = Rational<i64>;
+
+    #[test]
+    fn normalisation() {
+        let r = Q::new(6, -4);
+        assert_eq!((r.numer(), r.denom()), (-3, 2));
+        assert_eq!(Q::new(0, -7), Q::ZERO);
+        assert_eq!(Q::checked_new(1, 0), None);
+        assert_eq!(Q::new(i64::MIN, i64::MIN), Q::ONE);
+        assert_eq!(Q::checked_new(i64::MIN, -1), None);
+        assert_eq!(
+            Rational::<i128>::from(Q::new(-5, 10)),
+            Rational::<i128>::new(-1, 2)
+        );
+        assert_eq!(Q::try_from(Rational::<i128>::new(1, 1 << 70)).ok(), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Q::new(1, 2), Q::new(1, 3));
+        assert_eq!(a + b, Q::new(5, 6));
+        assert_eq!(a - b, Q::new(1, 6));
+        assert_eq!(a * b, Q::new(1, 6));
+        assert_eq!(a / b, Q::new(3, 2));
+        assert_eq!(1 - a * 3, -a);
+        assert_eq!((a / 5).recip(), Q::from(10));
+        assert_eq!(Q::new(-2, 3).pow(-3), Q::new(-27, 8));
+        assert_eq!((1..=4).map(|n| Q::new(1, n)).sum::<Q>(), Q::new(25, 12));
+        assert_eq!([a, b].iter().product::<Q>(), Q::new(1, 6));
+
+        let mut c = a;
+        c += 1;
+        c *= b;
+        c -= Q::new(1, 2);
+        c /= 2;
+        assert_eq!(c, Q::ZERO);
+
+        // Intermediate products would overflow without cancelling first.
+        let big = Q::new(i64::MAX, 3);
+        assert_eq!(big * Q::new(3, i64::MAX), Q::ONE);
+        assert_eq!(big.checked_mul(big), None);
+        assert_eq!(Q::new(1, i64::MAX).checked_div(Q::ZERO), None);
+        let max = Q::from_integer(i64::MAX);
+        assert_eq!(max.saturating_add(Q::ONE), max);
+        let half = Q::new(i64::MAX, 2);
+        assert_eq!(half.saturating_add(Q::from(i64::MAX / 2 + 1)), max);
+        assert_eq!(half.saturating_add(half), max);
+        assert_eq!((-max).saturating_add(Q::new(-1, 3)), -max);
+        let min = Q::from_integer(i64::MIN);
+        assert_eq!(min.checked_abs(), None);
+        assert_eq!(Q::new(-3, 4).abs(), Q::new(3, 4));
+    }
+
+    #[test]
+    fn saturating_in_range() {
+        // The sums are small, but their denominators exceed i64.
+        let (a, b) = (Q::new(1, i64::MAX), Q::new(1, i64::MAX - 1));
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.saturating_add(b), Q::new(1, 4611686018427387903));
+        let (a, b) = (Q::new(1, 1 << 40), Q::new(2, (1 << 40) - 1) + 1000);
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.saturating_add(b), Q::new(3298534883326009, 3298534883326));
+        type R = Rational<i128>;
+        let (a, b) = (R::new(1, i128::MAX), R::new(-1, i128::MAX - 2));
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.saturating_add(b), R::ZERO);
+    }
+
+    #[test]
+    fn rounding() {
+        let values = [(7, 2), (-7, 2), (5, 3), (-5, 3), (4, 1)];
+        let floors: Vec<i64> = values.iter().map(|&(n, d)| Q::new(n, d).floor()).collect();
+        let ceils: Vec<i64> = values.iter().map(|&(n, d)| Q::new(n, d).ceil()).collect();
+        let rounds: Vec<i64> = values.iter().map(|&(n, d)| Q::new(n, d).round()).collect();
+        let truncs: Vec<i64> = values.iter().map(|&(n, d)| Q::new(n, d).trunc()).collect();
+        assert_eq!(floors, vec![3, -4, 1, -2, 4]);
+        assert_eq!(ceils, vec![4, -3, 2, -1, 4]);
+        assert_eq!(rounds, vec![4, -4, 2, -2, 4]);
+        assert_eq!(truncs, vec![3, -3, 1, -1, 4]);
+        assert_eq!(Q::new(-7, 2).fract(), Q::new(1, 2));
+        assert_eq!(Q::new(-7, 2).to_integer(), None);
+        assert_eq!(Q::new(-8, 2).to_integer(), Some(-4));
+        assert_eq!(Q::new(-7, 4).to_f64(), -1.75);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut values: Vec<Q> = ["1/2", "-3", "2/3", "-7/2", "0", "5/8"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        values.sort();
+        assert_eq!(
+            values.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            vec!["-7/2", "-3", "0", "1/2", "5/8", "2/3"]
+        );
+
+        // Cross multiplication would overflow here.
+        type R = Rational<i128>;
+        let m = i128::MAX;
+        assert!(R::new(m, m - 1) < R::new(m - 1, m - 2));
+        assert!(R::new(-m, m - 1) > R::new(-(m - 1), m - 2));
+        assert!(R::new(1, m) > R::ZERO);
+        assert_eq!(R::new(m - 1, m).cmp(&R::new(m - 1, m)), Ordering::Equal);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("3/-6".parse(), Ok(Q::new(-1, 2)));
+        assert_eq!(" 42 ".parse(), Ok(Q::from(42)));
+        assert_eq!("1.25".parse(), Ok(Q::new(5, 4)));
+        assert_eq!("-0.5".parse(), Ok(Q::new(-1, 2)));
+        assert_eq!("-.5".parse(), Ok(Q::new(-1, 2)));
+        for s in ["1/0", "x", "1.", "1.-5", "1/2/3", ""] {
+            assert!(s.parse::<Q>().is_err(), "{s}");
+        }
+        assert_eq!(Q::new(-10, 4).to_string(), "-5/2");
+        assert_eq!(Q::new(10, 5).to_string(), "2");
+    }
+
+    #[test]
+    fn claw_machines() {
+        // Example from year 2024 day 13: solve a * A + b * B = prize exactly by Cramer's rule.
+        let solve = |a: (i64, i64), b: (i64, i64), prize: (i64, i64)| {
+            let det = Q::from(a.0 * b.1 - a.1 * b.0);
+            let presses_a = Q::from(prize.0 * b.1 - prize.1 * b.0) / det;
+            let presses_b = Q::from(a.0 * prize.1 - a.1 * prize.0) / det;
+            Some((presses_a.to_integer()?, presses_b.to_integer()?))
+        };
+        assert_eq!(solve((94, 34), (22, 67), (8400, 5400)), Some((80, 40)));
+        assert_eq!(solve((26, 66), (67, 21), (12748, 12176)), None);
+        assert_eq!(solve((17, 86), (84, 37), (7870, 6450)), Some((38, 86)));
+    }
+
+    #[test]
+    fn as_scalar() {
+        let z = Complex::new(Q::new(1, 2), Q::from(2));
+        let w = Complex::new(Q::from(3), Q::new(-1, 3));
+        assert_eq!(z / w * w, z);
+        assert_eq!((z * z.conj()).real, Q::new(17, 4));
+
+        // Solve a x + b y = c in the plane using the outer product.
+        let (a, b) = (
+            Multivec2D::vector(Q::from(2), Q::from(1)),
+            Multivec2D::vector(Q::from(1), Q::from(3)),
+        );
+        let c = Multivec2D::vector(Q::from(1), Q::from(1));
+        let wedge = |u: Multivec2D<Q>, v: Multivec2D<Q>| (u * v).grade_project(2).data[3];
+        let x = wedge(c, b) / wedge(a, b);
+        let y = wedge(a, c) / wedge(a, b);
+        assert_eq!((x, y), (Q::new(2, 5), Q::new(1, 5)));
+        assert_eq!(a * x + b * y, c);
+    }
+}
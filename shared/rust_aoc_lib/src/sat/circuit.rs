@@ -0,0 +1,390 @@
+// Declarative frontend for the SAT solver. Gates return a literal equal to their
+// output (Tseitin encoding), so formulas are built like expressions and only
+// assert adds them as constraints.
+
+use super::{Lit, Solver};
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    pub solver: Solver,
+    truth: Lit,
+}
+
+// Unsigned integer as bits, least significant first.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BitVec(pub Vec<Lit>);
+
+// Variable taking exactly one of the values, encoded with one literal per value.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct FiniteVar {
+    pub values: Vec<i64>,
+    pub lits: Vec<Lit>,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Circuit {
+    pub fn new() -> Self {
+        let mut solver = Solver::new();
+        let truth = solver.new_var();
+        solver.add_clause(&[truth]);
+        Self { solver, truth }
+    }
+
+    pub fn var(&mut self) -> Lit {
+        self.solver.new_var()
+    }
+
+    pub fn constant(&self, value: bool) -> Lit {
+        if value {
+            self.truth
+        } else {
+            !self.truth
+        }
+    }
+
+    // Require lit to be true.
+    pub fn assert(&mut self, lit: Lit) {
+        self.solver.add_clause(&[lit]);
+    }
+
+    pub fn solve(&mut self) -> bool {
+        self.solver.solve()
+    }
+
+    // Value in the last solution.
+    pub fn value(&self, lit: Lit) -> bool {
+        self.solver.value(lit)
+    }
+
+    pub fn and(&mut self, a: Lit, b: Lit) -> Lit {
+        self.all(&[a, b])
+    }
+
+    pub fn or(&mut self, a: Lit, b: Lit) -> Lit {
+        self.any(&[a, b])
+    }
+
+    pub fn all(&mut self, lits: &[Lit]) -> Lit {
+        let out = self.var();
+        for &lit in lits {
+            self.solver.add_clause(&[!out, lit]);
+        }
+        let mut clause: Vec<Lit> = lits.iter().map(|&lit| !lit).collect();
+        clause.push(out);
+        self.solver.add_clause(&clause);
+        return out;
+    }
+
+    pub fn any(&mut self, lits: &[Lit]) -> Lit {
+        let negated: Vec<Lit> = lits.iter().map(|&lit| !lit).collect();
+        !self.all(&negated)
+    }
+
+    pub fn xor(&mut self, a: Lit, b: Lit) -> Lit {
+        let out = self.var();
+        self.solver.add_clause(&[!out, a, b]);
+        self.solver.add_clause(&[!out, !a, !b]);
+        self.solver.add_clause(&[out, !a, b]);
+        self.solver.add_clause(&[out, a, !b]);
+        return out;
+    }
+
+    pub fn equal(&mut self, a: Lit, b: Lit) -> Lit {
+        !self.xor(a, b)
+    }
+
+    pub fn implies(&mut self, a: Lit, b: Lit) -> Lit {
+        self.or(!a, b)
+    }
+
+    // if cond then a else b
+    pub fn ite(&mut self, cond: Lit, a: Lit, b: Lit) -> Lit {
+        let out = self.var();
+        self.solver.add_clause(&[!cond, !a, out]);
+        self.solver.add_clause(&[!cond, a, !out]);
+        self.solver.add_clause(&[cond, !b, out]);
+        self.solver.add_clause(&[cond, b, !out]);
+        return out;
+    }
+
+    pub fn at_most_one(&mut self, lits: &[Lit]) {
+        for (i, &a) in lits.iter().enumerate() {
+            for &b in &lits[i + 1..] {
+                self.solver.add_clause(&[!a, !b]);
+            }
+        }
+    }
+
+    pub fn exactly_one(&mut self, lits: &[Lit]) {
+        self.solver.add_clause(lits);
+        self.at_most_one(lits);
+    }
+
+    pub fn bitvec(&mut self, width: usize) -> BitVec {
+        BitVec((0..width).map(|_| self.var()).collect())
+    }
+
+    pub fn bitvec_constant(&self, value: u64, width: usize) -> BitVec {
+        BitVec(
+            (0..width)
+                .map(|k| self.constant(value >> k & 1 == 1))
+                .collect(),
+        )
+    }
+
+    // Sum modulo 2^width of two bit vectors of equal width, by a ripple-carry adder.
+    pub fn add(&mut self, a: &BitVec, b: &BitVec) -> BitVec {
+        assert_eq!(a.0.len(), b.0.len(), "Bit vectors of different widths.");
+        let mut carry = self.constant(false);
+        let mut sum = vec![];
+        for (&x, &y) in a.0.iter().zip(&b.0) {
+            let half = self.xor(x, y);
+            sum.push(self.xor(half, carry));
+            let (both, propagated) = (self.and(x, y), self.and(half, carry));
+            carry = self.or(both, propagated);
+        }
+        return BitVec(sum);
+    }
+
+    pub fn bitvec_equal(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        assert_eq!(a.0.len(), b.0.len(), "Bit vectors of different widths.");
+        let bits: Vec<Lit> =
+            a.0.iter()
+                .zip(&b.0)
+                .map(|(&x, &y)| self.equal(x, y))
+                .collect();
+        self.all(&bits)
+    }
+
+    // Unsigned a < b.
+    pub fn less_than(&mut self, a: &BitVec, b: &BitVec) -> Lit {
+        assert_eq!(a.0.len(), b.0.len(), "Bit vectors of different widths.");
+        // From the least significant bit up, a more significant difference decides.
+        let mut less = self.constant(false);
+        for (&x, &y) in a.0.iter().zip(&b.0) {
+            let differ = self.xor(x, y);
+            less = self.ite(differ, y, less);
+        }
+        return less;
+    }
+
+    pub fn bitvec_value(&self, a: &BitVec) -> u64 {
+        a.0.iter()
+            .enumerate()
+            .map(|(k, &lit)| (self.value(lit) as u64) << k)
+            .sum()
+    }
+
+    pub fn finite(&mut self, values: &[i64]) -> FiniteVar {
+        let lits: Vec<Lit> = values.iter().map(|_| self.var()).collect();
+        self.exactly_one(&lits);
+        FiniteVar {
+            values: values.to_vec(),
+            lits,
+        }
+    }
+
+    // Literal for var == value, which is false for values outside its domain.
+    pub fn is(&self, var: &FiniteVar, value: i64) -> Lit {
+        match var.values.iter().position(|&v| v == value) {
+            Some(k) => var.lits[k],
+            None => self.constant(false),
+        }
+    }
+
+    pub fn all_different(&mut self, vars: &[FiniteVar]) {
+        let mut values: Vec<i64> = vars.iter().flat_map(|var| var.values.clone()).collect();
+        values.sort();
+        values.dedup();
+        for value in values {
+            let lits: Vec<Lit> = vars.iter().map(|var| self.is(var, value)).collect();
+            self.at_most_one(&lits);
+        }
+    }
+
+    pub fn finite_value(&self, var: &FiniteVar) -> i64 {
+        let k = var.lits.iter().position(|&lit| self.value(lit)).unwrap();
+        return var.values[k];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn gates() {
+        let mut circuit = Circuit::new();
+        let (a, b, c) = (circuit.var(), circuit.var(), circuit.var());
+        let gates = [
+            circuit.and(a, b),
+            circuit.or(a, b),
+            circuit.xor(a, b),
+            circuit.equal(a, b),
+            circuit.implies(a, b),
+            circuit.ite(c, a, b),
+        ];
+        // Every input combination is consistent with the gates.
+        for inputs in 0..8 {
+            let mut circuit = circuit.clone();
+            let bit = |k: usize| inputs >> k & 1 == 1;
+            for (k, lit) in [a, b, c].into_iter().enumerate() {
+                circuit.assert(if bit(k) { lit } else { !lit });
+            }
+            assert!(circuit.solve());
+            let outputs: Vec<bool> = gates.iter().map(|&g| circuit.value(g)).collect();
+            let (x, y, z) = (bit(0), bit(1), bit(2));
+            let expected = [
+                x && y,
+                x || y,
+                x ^ y,
+                x == y,
+                !x || y,
+                if z { x } else { y },
+            ];
+            assert_eq!(outputs, expected);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        // Find a < b with a + b == 13 (mod 16) and a == 6.
+        let mut circuit = Circuit::new();
+        let (a, b) = (circuit.bitvec(4), circuit.bitvec(4));
+        let sum = circuit.add(&a, &b);
+        let (thirteen, six) = (
+            circuit.bitvec_constant(13, 4),
+            circuit.bitvec_constant(6, 4),
+        );
+        for lit in [
+            circuit.bitvec_equal(&sum, &thirteen),
+            circuit.less_than(&a, &b),
+            circuit.bitvec_equal(&a, &six),
+        ] {
+            circuit.assert(lit);
+        }
+        assert!(circuit.solve());
+        assert_eq!((circuit.bitvec_value(&a), circuit.bitvec_value(&b)), (6, 7));
+
+        // Now a > b as well.
+        let greater = circuit.less_than(&b, &a);
+        circuit.assert(greater);
+        assert!(!circuit.solve());
+    }
+
+    #[test]
+    fn crossed_wires() {
+        // A two-bit adder as a wiring list, in the style of year 2024 day 24.
+        let adder = "\
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> h01
+x01 AND y01 -> a01
+h01 XOR c00 -> z01
+h01 AND c00 -> b01
+a01 OR b01 -> z02";
+        let build = |circuit: &mut Circuit, x: &BitVec, y: &BitVec, wiring: &str| {
+            let mut wires: HashMap<String, Lit> = HashMap::new();
+            for k in 0..2 {
+                wires.insert(format!("x{k:02}"), x.0[k]);
+                wires.insert(format!("y{k:02}"), y.0[k]);
+            }
+            for line in wiring.lines() {
+                let parts: Vec<&str> = line.split(' ').collect();
+                let (a, b) = (wires[parts[0]], wires[parts[2]]);
+                let out = match parts[1] {
+                    "AND" => circuit.and(a, b),
+                    "OR" => circuit.or(a, b),
+                    _ => circuit.xor(a, b),
+                };
+                wires.insert(parts[4].to_string(), out);
+            }
+            BitVec((0..3).map(|k| wires[&format!("z{k:02}")]).collect())
+        };
+        // Search for inputs where the wiring disagrees with addition.
+        let counterexample = |wiring: &str| {
+            let mut circuit = Circuit::new();
+            let (x, y) = (circuit.bitvec(2), circuit.bitvec(2));
+            let z = build(&mut circuit, &x, &y, wiring);
+            let zero = circuit.constant(false);
+            let extend = |v: &BitVec| BitVec(vec![v.0[0], v.0[1], zero]);
+            let sum = circuit.add(&extend(&x), &extend(&y));
+            let same = circuit.bitvec_equal(&z, &sum);
+            circuit.assert(!same);
+            circuit
+                .solve()
+                .then(|| (circuit.bitvec_value(&x), circuit.bitvec_value(&y)))
+        };
+        assert_eq!(counterexample(adder), None);
+
+        // Swap the outputs of two gates.
+        let swapped = adder
+            .replace("-> z01", "-> tmp")
+            .replace("-> b01", "-> z01");
+        let swapped = swapped.replace("-> tmp", "-> b01");
+        let (x, y) = counterexample(&swapped).unwrap();
+        assert!((x ^ y) & 2 != 0 || x & y & 1 != 0);
+    }
+
+    #[test]
+    fn sudoku() {
+        // A 4 x 4 sudoku with 2 x 2 boxes and a unique solution.
+        let clues = "\
+1...
+..2.
+.3..
+...4";
+        let mut circuit = Circuit::new();
+        let cells: Vec<Vec<FiniteVar>> = (0..4)
+            .map(|_| (0..4).map(|_| circuit.finite(&[1, 2, 3, 4])).collect())
+            .collect();
+        for (i, line) in clues.lines().enumerate() {
+            for (j, c) in line.chars().enumerate() {
+                if let Some(d) = c.to_digit(10) {
+                    let lit = circuit.is(&cells[i][j], d as i64);
+                    circuit.assert(lit);
+                }
+            }
+        }
+        for k in 0..4 {
+            let row = cells[k].clone();
+            let column: Vec<FiniteVar> = (0..4).map(|i| cells[i][k].clone()).collect();
+            let block: Vec<FiniteVar> = (0..4)
+                .map(|c| cells[2 * (k / 2) + c / 2][2 * (k % 2) + c % 2].clone())
+                .collect();
+            for group in [row, column, block] {
+                circuit.all_different(&group);
+            }
+        }
+
+        assert!(circuit.solve());
+        let solution: Vec<Vec<i64>> = cells
+            .iter()
+            .map(|row| row.iter().map(|cell| circuit.finite_value(cell)).collect())
+            .collect();
+        assert_eq!(
+            solution,
+            vec![
+                vec![1, 2, 4, 3],
+                vec![3, 4, 2, 1],
+                vec![4, 3, 1, 2],
+                vec![2, 1, 3, 4]
+            ]
+        );
+
+        // The solution is unique.
+        let lits: Vec<Lit> = cells
+            .iter()
+            .flatten()
+            .map(|cell| !circuit.is(cell, circuit.finite_value(cell)))
+            .collect();
+        circuit.solver.add_clause(&lits);
+        assert!(!circuit.solve());
+    }
+}
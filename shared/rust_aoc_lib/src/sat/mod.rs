@@ -0,0 +1,5 @@
+mod circuit;
+mod solver;
+
+pub use circuit::*;
+pub use solver::*;
@@ -0,0 +1,428 @@
+// Conflict-driven clause learning SAT solver: two watched literals for unit propagation,
+// first-UIP learning with non-chronological backjumping, activity-based decisions with
+// phase saving, and Luby restarts. Clauses can be added between calls to solve.
+
+use std::ops::Not;
+
+// A boolean variable or its negation.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Lit(self.0 ^ 1)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Solver {
+    clauses: Vec<Vec<Lit>>,
+    // Clauses watching each literal, which are visited when it becomes false.
+    watches: Vec<Vec<usize>>,
+    assigns: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    // The clause that implied each propagated variable.
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    // Trail length at the start of each decision level.
+    trail_lim: Vec<usize>,
+    queue_head: usize,
+    activity: Vec<f64>,
+    activity_inc: f64,
+    phases: Vec<bool>,
+    model: Vec<bool>,
+    unsatisfiable: bool,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self {
+            activity_inc: 1.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_var(&mut self) -> Lit {
+        let var = self.assigns.len();
+        self.assigns.push(None);
+        self.levels.push(0);
+        self.reasons.push(None);
+        self.activity.push(0.0);
+        self.phases.push(false);
+        self.watches.extend([vec![], vec![]]);
+        return Lit(2 * var as u32);
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.assigns.len()
+    }
+
+    fn lit_value(&self, lit: Lit) -> Option<bool> {
+        self.assigns[lit.var()].map(|value| value != lit.is_negated())
+    }
+
+    fn level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    // Require at least one literal of the clause to be true. An empty clause makes
+    // the problem unsatisfiable.
+    pub fn add_clause(&mut self, clause: &[Lit]) {
+        if self.unsatisfiable {
+            return;
+        }
+        let mut clause = clause.to_vec();
+        clause.sort();
+        clause.dedup();
+        // Drop clauses that always hold and literals that are false for good.
+        if clause.windows(2).any(|w| w[1] == !w[0])
+            || clause.iter().any(|&l| self.lit_value(l) == Some(true))
+        {
+            return;
+        }
+        clause.retain(|&l| self.lit_value(l).is_none());
+        match clause.len() {
+            0 => self.unsatisfiable = true,
+            1 => {
+                self.assign(clause[0], None);
+                self.unsatisfiable = self.propagate().is_some();
+            }
+            _ => {
+                self.attach(clause);
+            }
+        }
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let index = self.clauses.len();
+        self.watches[clause[0].index()].push(index);
+        self.watches[clause[1].index()].push(index);
+        self.clauses.push(clause);
+        return index;
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var();
+        self.assigns[var] = Some(!lit.is_negated());
+        self.levels[var] = self.level();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    // Unit propagation, returning a conflicting clause if there is one.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.queue_head < self.trail.len() {
+            let false_lit = !self.trail[self.queue_head];
+            self.queue_head += 1;
+            let watching = std::mem::take(&mut self.watches[false_lit.index()]);
+            let mut kept = Vec::with_capacity(watching.len());
+            let mut conflict = None;
+            for (k, &c) in watching.iter().enumerate() {
+                if conflict.is_some() {
+                    kept.extend_from_slice(&watching[k..]);
+                    break;
+                }
+                // Keep the false literal in position 1.
+                if self.clauses[c][0] == false_lit {
+                    self.clauses[c].swap(0, 1);
+                }
+                let first = self.clauses[c][0];
+                if self.lit_value(first) == Some(true) {
+                    kept.push(c);
+                    continue;
+                }
+                let replacement = (2..self.clauses[c].len())
+                    .find(|&i| self.lit_value(self.clauses[c][i]) != Some(false));
+                if let Some(i) = replacement {
+                    self.clauses[c].swap(1, i);
+                    self.watches[self.clauses[c][1].index()].push(c);
+                    continue;
+                }
+                kept.push(c);
+                if self.lit_value(first) == Some(false) {
+                    conflict = Some(c);
+                } else {
+                    self.assign(first, Some(c));
+                }
+            }
+            self.watches[false_lit.index()] = kept;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        return None;
+    }
+
+    // First-UIP clause learnt from a conflict, with the asserting literal first and
+    // a literal of the backjump level second, and that level.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut seen = vec![false; self.num_vars()];
+        let mut learnt = vec![Lit(0)];
+        let mut pending = 0;
+        let mut clause = conflict;
+        let mut index = self.trail.len();
+        // Reason clauses have the implied literal first, which is already seen.
+        let mut start = 0;
+        loop {
+            for k in start..self.clauses[clause].len() {
+                let lit = self.clauses[clause][k];
+                let var = lit.var();
+                if seen[var] || self.levels[var] == 0 {
+                    continue;
+                }
+                seen[var] = true;
+                self.bump(var);
+                if self.levels[var] == self.level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+            // Continue with the most recently assigned literal involved in the conflict.
+            let lit = loop {
+                index -= 1;
+                if seen[self.trail[index].var()] {
+                    break self.trail[index];
+                }
+            };
+            seen[lit.var()] = false;
+            pending -= 1;
+            if pending == 0 {
+                learnt[0] = !lit;
+                break;
+            }
+            clause = self.reasons[lit.var()].expect("Implied literal without a reason.");
+            start = 1;
+        }
+
+        let mut backjump = 0;
+        if learnt.len() > 1 {
+            let k = (1..learnt.len())
+                .max_by_key(|&k| self.levels[learnt[k].var()])
+                .unwrap();
+            learnt.swap(1, k);
+            backjump = self.levels[learnt[1].var()];
+        }
+        return (learnt, backjump);
+    }
+
+    fn bump(&mut self, var: usize) {
+        self.activity[var] += self.activity_inc;
+        if self.activity[var] > 1e100 {
+            self.activity.iter_mut().for_each(|a| *a *= 1e-100);
+            self.activity_inc *= 1e-100;
+        }
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if self.level() <= level {
+            return;
+        }
+        for &lit in &self.trail[self.trail_lim[level]..] {
+            self.phases[lit.var()] = !lit.is_negated();
+            self.assigns[lit.var()] = None;
+        }
+        self.trail.truncate(self.trail_lim[level]);
+        self.trail_lim.truncate(level);
+        self.queue_head = self.trail.len();
+    }
+
+    // Unassigned variable with the highest activity, with its last value.
+    fn decide(&self) -> Option<Lit> {
+        let var = (0..self.num_vars())
+            .filter(|&v| self.assigns[v].is_none())
+            .max_by(|&a, &b| self.activity[a].total_cmp(&self.activity[b]))?;
+        let lit = Lit(2 * var as u32);
+        return Some(if self.phases[var] { lit } else { !lit });
+    }
+
+    // Search for an assignment satisfying all clauses, which value then reads.
+    pub fn solve(&mut self) -> bool {
+        if self.unsatisfiable || self.propagate().is_some() {
+            self.unsatisfiable = true;
+            return false;
+        }
+        let (mut conflicts, mut restarts) = (0, 0);
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.level() == 0 {
+                    self.unsatisfiable = true;
+                    return false;
+                }
+                let (learnt, backjump) = self.analyze(conflict);
+                self.backtrack(backjump);
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let index = self.attach(learnt);
+                    self.assign(asserting, Some(index));
+                }
+                self.activity_inc *= 1.05;
+                conflicts += 1;
+                if conflicts >= 100 * luby(restarts) {
+                    (conflicts, restarts) = (0, restarts + 1);
+                    self.backtrack(0);
+                }
+                continue;
+            }
+            let Some(lit) = self.decide() else {
+                self.model = self.assigns.iter().map(|a| a.unwrap()).collect();
+                self.backtrack(0);
+                return true;
+            };
+            self.trail_lim.push(self.trail.len());
+            self.assign(lit, None);
+        }
+    }
+
+    // Value of a literal in the last satisfying assignment. Panics if there is none.
+    pub fn value(&self, lit: Lit) -> bool {
+        self.model[lit.var()] != lit.is_negated()
+    }
+}
+
+// The Luby sequence 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, ...
+fn luby(mut k: u64) -> u64 {
+    let mut size = 1;
+    while size < k + 1 {
+        size = 2 * size + 1;
+    }
+    while size - 1 != k {
+        size /= 2;
+        k %= size;
+    }
+    return size.div_ceil(2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_schedule() {
+        let sequence: Vec<u64> = (0..15).map(luby).collect();
+        assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn pigeonholes() {
+        // n + 1 pigeons do not fit into n holes, but n pigeons do.
+        let fits = |pigeons: usize, holes: usize| {
+            let mut solver = Solver::new();
+            let x: Vec<Vec<Lit>> = (0..pigeons)
+                .map(|_| (0..holes).map(|_| solver.new_var()).collect())
+                .collect();
+            for row in &x {
+                solver.add_clause(row);
+            }
+            // No two pigeons share a hole.
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    for (&a, &b) in x[p].iter().zip(&x[q]) {
+                        solver.add_clause(&[!a, !b]);
+                    }
+                }
+            }
+            solver.solve()
+        };
+        assert!(fits(5, 5));
+        assert!(!fits(6, 5));
+    }
+
+    #[test]
+    fn incremental() {
+        // Count the solutions of (a or b) and (not a or c) by blocking each one found.
+        let mut solver = Solver::new();
+        let vars: Vec<Lit> = (0..3).map(|_| solver.new_var()).collect();
+        let (a, b, c) = (vars[0], vars[1], vars[2]);
+        solver.add_clause(&[a, b]);
+        solver.add_clause(&[!a, c]);
+        let mut count = 0;
+        while solver.solve() {
+            assert!(solver.value(a) || solver.value(b));
+            assert!(!solver.value(a) || solver.value(c));
+            let blocking: Vec<Lit> = vars
+                .iter()
+                .map(|&v| if solver.value(v) { !v } else { v })
+                .collect();
+            solver.add_clause(&blocking);
+            count += 1;
+        }
+        assert_eq!(count, 4);
+        assert!(!solver.solve());
+
+        let mut solver = Solver::new();
+        let x = solver.new_var();
+        solver.add_clause(&[x, !x]);
+        assert!(solver.solve());
+        solver.add_clause(&[!x]);
+        assert!(solver.solve() && !solver.value(x));
+        solver.add_clause(&[x]);
+        assert!(!solver.solve());
+    }
+
+    #[test]
+    fn random_instances() {
+        // Compare against brute force on random 3-SAT near the satisfiability threshold.
+        let mut seed: u64 = 12345;
+        let mut random = |n: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+        let (vars, clauses) = (12, 52);
+        let mut satisfiable = 0;
+        for _ in 0..100 {
+            let mut solver = Solver::new();
+            let x: Vec<Lit> = (0..vars).map(|_| solver.new_var()).collect();
+            let formula: Vec<Vec<Lit>> = (0..clauses)
+                .map(|_| {
+                    (0..3)
+                        .map(|_| {
+                            let lit = x[random(vars) as usize];
+                            if random(2) == 0 {
+                                lit
+                            } else {
+                                !lit
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            for clause in &formula {
+                solver.add_clause(clause);
+            }
+            let holds = |value: &dyn Fn(Lit) -> bool| {
+                formula
+                    .iter()
+                    .all(|clause| clause.iter().any(|&l| value(l)))
+            };
+            let brute_force = (0..1 << vars)
+                .any(|bits: u32| holds(&|l: Lit| (bits >> l.var() & 1 == 1) != l.is_negated()));
+            assert_eq!(solver.solve(), brute_force);
+            if brute_force {
+                assert!(holds(&|l| solver.value(l)));
+                satisfiable += 1;
+            }
+        }
+        assert!(0 < satisfiable && satisfiable < 100);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::num::Integer;
+use std::ops::Neg;
+
 pub fn floyd<T: Clone + Eq>(x0: T, map: &dyn Fn(&T) -> T) -> (usize, usize) {
     // Floyd's cycle detection algorithm.
     // Returns tuple (cycle period, cycle start)
@@ -37,31 +40,66 @@ pub fn floyd<T: Clone + Eq>(x0: T, map: &dyn Fn(&T) -> T) -> (usize, usize) {
     return (lambda, mu);
 }
 
-pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
     // Euclidean algorithm for determining the
-    // Greatest Commen Divisor (GCD)
-    while b != 0 {
-        (a, b) = (b, a % b);
+    // Greatest Commen Divisor (GCD), which is never negative.
+    // Panics if it is not representable, e.g. gcd(i64::MIN, 0).
+    return checked_gcd(a, b).expect("gcd overflow.");
+}
+
+pub fn checked_gcd<T: Integer>(mut a: T, mut b: T) -> Option<T> {
+    // gcd, or None if it is not representable.
+    // Remainders by +-1 are 0, which also avoids the overflow of i64::MIN % -1.
+    while b != T::ZERO {
+        let r = if b.checked_abs() == Some(T::ONE) { T::ZERO } else { a % b };
+        (a, b) = (b, r);
     }
-    return a;
+    return a.checked_abs();
 }
 
-pub fn lcm(a: u64, b: u64) -> u64 {
-    // Least Common Multiple (LCM)
-    return (a / gcd(a, b)) * b;
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    // Least Common Multiple (LCM), never negative and 0 if a or b is.
+    // Panics on overflow.
+    return checked_lcm(a, b).expect("lcm overflow.");
+}
+
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    // lcm, or None if it is not representable.
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    return (a / checked_gcd(a, b)?).checked_mul(b)?.checked_abs();
 }
 
-pub fn bezout(a: i64, b: i64) -> (i64, i64, i64) {
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    // gcd of all values, 0 if there are none.
+    return values.into_iter().fold(T::ZERO, gcd);
+}
+
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    // lcm of all values, 1 if there are none. Panics on overflow.
+    return checked_lcm_all(values).expect("lcm overflow.");
+}
+
+pub fn checked_lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    // lcm_all, or None if it is not representable.
+    return values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| checked_lcm(acc, value));
+}
+
+pub fn bezout<T: Integer + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
     // Extended Euclidean algorithm.
     // Returns (gcd, s, t) such that Bezout's identity holds: 
     // gcd(a, b) = a*s + b*t
+    // The Neg bound only excludes unsigned types, as s or t is negative in general.
 
     let mut r = (a, b);
-    let mut s = (1_i64, 0_i64);
-    let mut t = (0_i64, 1_i64);
+    let mut s = (T::ONE, T::ZERO);
+    let mut t = (T::ZERO, T::ONE);
     let mut q;
 
-    while r.1 != 0 {
+    while r.1 != T::ZERO {
         q = r.0 / r.1;
         r = (r.1, r.0 - q * r.1);
         s = (s.1, s.0 - q * s.1);
@@ -71,30 +109,76 @@ pub fn bezout(a: i64, b: i64) -> (i64, i64, i64) {
     return (r.0, s.0, t.0);
 }
 
-pub fn crt_solve(n_vec: &Vec<i64>, a_vec: &Vec<i64>) -> Option<(i64, i64)> {
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CrtError {
+    // A modulus is zero or negative.
+    InvalidModulus,
+    // The congruences contradict each other.
+    NoSolution,
+    // There is a solution, but the combined modulus does not fit in the type (or i128).
+    Overflow,
+}
+
+impl std::fmt::Display for CrtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrtError::InvalidModulus => write!(f, "Moduli must be positive."),
+            CrtError::NoSolution => write!(f, "No solution."),
+            CrtError::Overflow => write!(f, "Combined modulus does not fit."),
+        }
+    }
+}
+
+pub fn crt_solve<T>(n_vec: &[T], a_vec: &[T]) -> Result<(T, T), CrtError>
+where
+    T: Copy + Into<i128> + TryFrom<i128>,
+{
     // Chinese Remainder Theorem (CRT).
     // Returns tuple (a, n) such that all x (including x=a) for which x % n = a solves 
     // the simultaneous congruence relations: x = a_i mod n_i.
+    // Works in i128 without intermediate overflow.
 
-    let (mut n0, mut a0) = (1, 0);
-    for (n1, a1) in std::iter::zip(n_vec, a_vec) {
-        let (gcd, m0, m1) = bezout(n0, *n1);
-        let mut x = a1 * m0 * n0 + a0 * m1 * n1;
-        
-        if gcd != 1 { // Non-coprime moduli
-            if (a0 - a1) % gcd != 0 { // No solution exists
-                return None
-            } else {
-                x /= gcd;
-                n0 /= gcd;
-            }
+    let (mut n0, mut a0) = (1_i128, 0_i128);
+    for (&n1, &a1) in std::iter::zip(n_vec, a_vec) {
+        let n1: i128 = n1.into();
+        if n1 <= 0 {
+            return Err(CrtError::InvalidModulus);
         }
-        
-        n0 = n0 * n1;
-        a0 = x % n0;
+        let a1 = a1.into().rem_euclid(n1);
+        let (gcd, m0, _) = bezout(n0, n1);
+        if (a1 - a0) % gcd != 0 {
+            // No solution exists
+            return Err(CrtError::NoSolution);
+        }
+
+        // x = a0 + n0 * k solves both when n0 * k = a1 - a0 mod n1,
+        // so k = m0 * (a1 - a0) / gcd mod n1 / gcd.
+        let step = n1 / gcd;
+        let k = mul_mod(((a1 - a0) / gcd).rem_euclid(step), m0.rem_euclid(step), step);
+        let n = n0.checked_mul(step).ok_or(CrtError::Overflow)?;
+        a0 = (a0.rem_euclid(n0) + n0 * k) % n;
+        n0 = n;
     }
 
-    return if a0 >= 0 { Some((a0, n0)) } else { Some((a0 + n0, n0)) };
+    let fits = |x: i128| T::try_from(x).map_err(|_| CrtError::Overflow);
+    return Ok((fits(a0)?, fits(n0)?));
+}
+
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    // a * b mod m for 0 <= a, b < m, by doubling where the product would overflow.
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (mut a, mut b, m) = (a as u128, b as u128, m as u128);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    return result as i128;
 }
 
 pub fn extrapolate(sequence: &Vec<i64>, n: usize) -> i64 {
@@ -153,6 +237,34 @@ mod tests {
         assert_eq!(lcm(6237, 3465), 31185);
     }
 
+    #[test]
+    fn test_generic_gcd() {
+        assert_eq!(gcd(-12_i32, 18), 6);
+        assert_eq!(gcd(0_u8, 0), 0);
+        assert_eq!(gcd(1_u128 << 100, 3 << 40), 1 << 40);
+        assert_eq!(checked_gcd(i64::MIN, 0), None);
+        assert_eq!(checked_gcd(i64::MIN, 6), Some(2));
+        assert_eq!(checked_gcd(i64::MIN, -1), Some(1));
+        assert_eq!(gcd(-1, i64::MIN), 1);
+        assert_eq!(checked_lcm(i64::MIN, -1), None);
+        assert_eq!(lcm(-(1_i64 << 62), -1), 1 << 62);
+        assert_eq!(gcd_all([12_usize, 18, 30]), 6);
+        assert_eq!(gcd_all(Vec::<i16>::new()), 0);
+    }
+
+    #[test]
+    fn test_generic_lcm() {
+        assert_eq!(lcm(-4_i64, 6), 12);
+        assert_eq!(lcm(0_u32, 7), 0);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5, 6]), 60);
+        assert_eq!(lcm_all((1..=20).map(|n: u128| n)), 232792560);
+        assert_eq!(checked_lcm(200_u8, 3), None);
+        assert_eq!(checked_lcm(i32::MIN, 1), None);
+        // The lcm of 1..=47 overflows u64, but not that of 1..=46.
+        assert_eq!(checked_lcm_all(1..=46_u64), Some(9419588158802421600));
+        assert_eq!(checked_lcm_all(1..=47_u64), None);
+    }
+
     #[test]
     fn test_bezout() {
         let (a, b) = (177741, 149553);
@@ -160,6 +272,10 @@ mod tests {
 
         assert_eq!(gcd_, gcd(177741, 149553) as i64);
         assert_eq!(gcd_, a * s + b * t);
+
+        let (a, b) = (1_i128 << 60, 3_i128.pow(38));
+        let (gcd_, s, t) = bezout(a, b);
+        assert_eq!((gcd_, a * s + b * t), (1, 1));
     }
 
     #[test]
@@ -173,7 +289,7 @@ mod tests {
     fn test_crt() {
         let n_vec = vec![3, 4, 5];
         let a_vec = vec![0, 3, 4];
-        assert_eq!(crt_solve(&n_vec, &a_vec), Some((39, 60)));
+        assert_eq!(crt_solve(&n_vec, &a_vec), Ok((39, 60)));
     }
 
     #[test]
@@ -181,13 +297,45 @@ mod tests {
         let n_vec = vec![2, 3, 4, 5, 6];
         
         let a_vec = vec![1, 1, 1, 1, 1];
-        assert_eq!(crt_solve(&n_vec, &a_vec), Some((1, 60)));
+        assert_eq!(crt_solve(&n_vec, &a_vec), Ok((1, 60)));
         
         let a_vec = vec![1, 1, 3, 0, 1];
-        assert_eq!(crt_solve(&n_vec, &a_vec), Some((55, 60)));
+        assert_eq!(crt_solve(&n_vec, &a_vec), Ok((55, 60)));
 
         let a_vec = vec![1, 1, 0, 1, 1];
-        assert_eq!(crt_solve(&n_vec, &a_vec), None);
+        assert_eq!(crt_solve(&n_vec, &a_vec), Err(CrtError::NoSolution));
+
+        // Residues are taken modulo their moduli, which must be positive.
+        assert_eq!(crt_solve(&[3, 5], &[-1, 12]), Ok((2, 15)));
+        assert_eq!(crt_solve(&[3, 0], &[1, 1]), Err(CrtError::InvalidModulus));
+        assert_eq!(crt_solve(&[3, -5], &[1, 1]), Err(CrtError::InvalidModulus));
+    }
+
+    #[test]
+    fn test_crt_large_moduli() {
+        // a1 * m0 * n0 would overflow i64 here. The system is solvable, but the
+        // combined modulus of about 1e27 does not fit in i64.
+        let n_vec: Vec<i64> = vec![1_000_000_007, 998_244_353, 1_000_000_009];
+        let a_vec = vec![5, 7, 11];
+        assert_eq!(crt_solve(&n_vec, &a_vec), Err(CrtError::Overflow));
+        let wide: Vec<i128> = n_vec.iter().map(|&n| n as i128).collect();
+        let (a, n) = crt_solve(&wide, &[5, 7, 11]).unwrap();
+        assert_eq!(n, 1_000_000_007 * 998_244_353 * 1_000_000_009);
+        for (n_i, a_i) in [(1_000_000_007, 5), (998_244_353, 7), (1_000_000_009, 11)] {
+            assert_eq!(a % n_i, a_i);
+        }
+
+        let n_vec: Vec<i64> = vec![1_000_000_007, 998_244_353];
+        let (a, n) = crt_solve(&n_vec, &[5, 7]).unwrap();
+        assert_eq!(n, 1_000_000_007 * 998_244_353);
+        assert_eq!((a % 1_000_000_007, a % 998_244_353), (5, 7));
+
+        // A modulus near 2^100, where even i128 products of residues overflow.
+        let (p, q) = (3_i128, (1_i128 << 100) + 1);
+        let (a, n) = crt_solve(&[p, q], &[2, q - 5]).unwrap();
+        assert_eq!((a % p, a % q, n), (2, q - 5, p * q));
+        // The combined modulus does not fit in i128.
+        assert_eq!(crt_solve(&[q, q + 2], &[1, 2]), Err(CrtError::Overflow));
     }
 
     #[test]
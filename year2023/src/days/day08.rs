@@ -1,4 +1,4 @@
-use rust_aoc_lib::utils::lcm;
+use rust_aoc_lib::utils::lcm_all;
 
 type Node = (String, (String, String));
 type DataStruct = Vec<Node>;
@@ -15,10 +15,7 @@ pub fn run(use_test_input: bool) {
     // PART 2
     let starts = map.iter().filter(|x| x.0.chars().last().unwrap() == 'A');
     let lengths = starts.map(|node: &Node| length(node, &map, &directions, true));
-    println!(
-        "Result part 2: {}",
-        lengths.fold(1, |acc, length| lcm(acc, length))
-    );
+    println!("Result part 2: {}", lcm_all(lengths));
 }
 
 fn length(start: &Node, map: &DataStruct, directions: &Vec<char>, part2: bool) -> u64 {
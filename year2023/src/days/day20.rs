@@ -1,4 +1,4 @@
-use rust_aoc_lib::utils::lcm;
+use rust_aoc_lib::utils::lcm_all;
 use std::collections::{HashMap, VecDeque};
 
 pub fn run(use_test_input: bool) {
@@ -95,10 +95,7 @@ pub fn run(use_test_input: bool) {
     }
 
     // dbg!(&periods);
-    println!(
-        "Result part 2: {}",
-        periods.iter().fold(1, |res, p| lcm(res, *p))
-    );
+    println!("Result part 2: {}", lcm_all(periods));
 }
 
 #[derive(Debug, Default, Clone)]
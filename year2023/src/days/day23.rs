@@ -90,7 +90,8 @@ fn compute_graph(map: &Vec<Vec<char>>, part2: bool) -> Graph {
         if neighbours.len() == 2 {
             let combined_weight = graph[&node].values().sum();
             graph.remove_node(&node);
-            graph.add_edge(neighbours[0], neighbours[1], combined_weight);
+            graph.add_directed_edge(neighbours[0], neighbours[1], combined_weight);
+            graph.add_directed_edge(neighbours[1], neighbours[0], combined_weight);
         }
     }
 
@@ -1,9 +1,9 @@
-use rust_aoc_lib::graph::{algs::min_cut, Graph};
+use rust_aoc_lib::graph::{algs::min_cut, UnGraph};
 
 pub fn run(use_test_input: bool) {
     let input = super::read_input(25, use_test_input);
 
-    let mut graph = Graph::<String, i64>::new();
+    let mut graph = UnGraph::<String, i64>::new_undirected();
     for line in input.trim().split('\n') {
         let args: Vec<&str> = line.split(' ').collect();
 
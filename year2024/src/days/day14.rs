@@ -89,19 +89,17 @@ pub fn run(use_test_input: bool) {
     }
 
     // A bit cumbersome due to f32 not having an implementation of Ord
-    let index_of_min = |vec: &Vec<f32>| -> i64 {
+    let index_of_min = |vec: &Vec<f32>| -> i32 {
         vec.iter()
             .enumerate()
             .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
             .unwrap()
-            .0 as i64
+            .0 as i32
     };
 
     // Frame of minimal entropy
-    let a_vec = vec![index_of_min(&x_entropies), index_of_min(&y_entropies)];
-    let image_frame = crt_solve(&vec![bounds[0] as i64, bounds[1] as i64], &a_vec)
-        .unwrap()
-        .0 as i32;
+    let a_vec = [index_of_min(&x_entropies), index_of_min(&y_entropies)];
+    let image_frame = crt_solve(&bounds, &a_vec).unwrap().0;
 
     println!("Result part 1: {part1}");
     println!("Result part 2: {image_frame}");
@@ -1,10 +1,10 @@
-use rust_aoc_lib::graph::Graph;
+use rust_aoc_lib::graph::UnGraph;
 use rust_aoc_lib::graph::algs::max_cliques;
 
 pub fn run(use_test_input: bool) {
     let input = super::read_input(23, use_test_input);
 
-    let mut network = Graph::new();
+    let mut network = UnGraph::new_undirected();
     for line in input.lines() {
         let (a, b) = line.split_once('-').unwrap();
         network.add_edge(a, b, ());